@@ -0,0 +1,65 @@
+use anyhow::{anyhow, Result};
+use aoc_common::{input::DefaultInput, parse, solution::Solution};
+
+/// where to find the puzzle input by default
+pub const INPUT: DefaultInput = aoc_common::default_input!();
+
+/// entries in the expense report must sum to this
+const TARGET: i32 = 2020;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    /// sorted expense report entries
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    /// ingest/parse file (one integer per line)
+    fn parse(input_txt: &str) -> Result<Self::Input> {
+        let mut v: Vec<i32> = parse::lines(input_txt)
+            .map(|l| l.field(l.text, "an expense"))
+            .collect::<Result<_, _>>()?;
+        v.sort();
+        Ok(v)
+    }
+
+    /// part 1: product of the pair that sums to 2020
+    fn part1(v: &Self::Input) -> Result<i32> {
+        // brute-force checks of combinations
+        for (idx, a) in v.iter().enumerate() {
+            for b in &v[idx + 1..] {
+                if a + b == TARGET {
+                    return Ok(a * b);
+                } else if a + b > TARGET {
+                    // sorted, so no later b can work either
+                    break;
+                }
+            }
+        }
+
+        Err(anyhow!("no pair sums to {}", TARGET))
+    }
+
+    /// part 2: product of the triple that sums to 2020
+    fn part2(v: &Self::Input) -> Result<i32> {
+        // brute-force checks of combinations
+        for (idx, a) in v.iter().enumerate() {
+            for (n, b) in v.iter().enumerate().skip(idx + 1) {
+                if a + b > TARGET {
+                    break;
+                }
+
+                for c in &v[n + 1..] {
+                    if a + b + c == TARGET {
+                        return Ok(a * b * c);
+                    } else if a + b + c > TARGET {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Err(anyhow!("no triple sums to {}", TARGET))
+    }
+}
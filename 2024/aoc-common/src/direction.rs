@@ -0,0 +1,122 @@
+/// direction that something on a grid is facing
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Orientation {
+    /// all orientations, clockwise starting from `Up`
+    pub const ALL: [Orientation; 4] = [
+        Orientation::Up,
+        Orientation::Right,
+        Orientation::Down,
+        Orientation::Left,
+    ];
+
+    /// orientation after turning right 90 degrees
+    pub fn turn_right(&self) -> Self {
+        match self {
+            Orientation::Up => Orientation::Right,
+            Orientation::Right => Orientation::Down,
+            Orientation::Down => Orientation::Left,
+            Orientation::Left => Orientation::Up,
+        }
+    }
+
+    /// orientation after turning left 90 degrees
+    pub fn turn_left(&self) -> Self {
+        match self {
+            Orientation::Up => Orientation::Left,
+            Orientation::Right => Orientation::Up,
+            Orientation::Down => Orientation::Right,
+            Orientation::Left => Orientation::Down,
+        }
+    }
+
+    /// orientation after turning around
+    pub fn reverse(&self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    /// (row, col) offset of a single step in this orientation
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            Orientation::Up => (-1, 0),
+            Orientation::Right => (0, 1),
+            Orientation::Down => (1, 0),
+            Orientation::Left => (0, -1),
+        }
+    }
+
+    /// (row, col) one step from `(row, col)` on a `rows` x `cols` grid, or `None` if that
+    /// would leave the grid
+    pub fn step(&self, row: usize, col: usize, rows: usize, cols: usize) -> Option<(usize, usize)> {
+        let (dr, dc) = self.delta();
+        let new_row = row.checked_add_signed(dr)?;
+        let new_col = col.checked_add_signed(dc)?;
+
+        if new_row < rows && new_col < cols {
+            Some((new_row, new_col))
+        } else {
+            None
+        }
+    }
+
+    /// (row, col) one step from `(row, col)` on a `rows` x `cols` grid whose opposite edges are
+    /// joined, as a torus
+    pub fn step_wrapping(
+        &self,
+        row: usize,
+        col: usize,
+        rows: usize,
+        cols: usize,
+    ) -> (usize, usize) {
+        let (dr, dc) = self.delta();
+        (
+            (row + rows).wrapping_add_signed(dr) % rows,
+            (col + cols).wrapping_add_signed(dc) % cols,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::direction::*;
+
+    #[test]
+    fn test_turns() {
+        for o in Orientation::ALL {
+            assert_eq!(o, o.turn_right().turn_left());
+            assert_eq!(o, o.reverse().reverse());
+            assert_ne!(o, o.reverse());
+        }
+        assert_eq!(Orientation::Right, Orientation::Up.turn_right());
+        assert_eq!(Orientation::Down, Orientation::Up.reverse());
+    }
+
+    #[test]
+    fn test_step() {
+        assert_eq!(None, Orientation::Up.step(0, 3, 10, 10));
+        assert_eq!(None, Orientation::Left.step(3, 0, 10, 10));
+        assert_eq!(None, Orientation::Down.step(9, 3, 10, 10));
+        assert_eq!(None, Orientation::Right.step(3, 9, 10, 10));
+        assert_eq!(Some((2, 3)), Orientation::Up.step(3, 3, 10, 10));
+        assert_eq!(Some((3, 4)), Orientation::Right.step(3, 3, 10, 10));
+    }
+
+    #[test]
+    fn test_step_wrapping() {
+        assert_eq!((9, 3), Orientation::Up.step_wrapping(0, 3, 10, 10));
+        assert_eq!((3, 9), Orientation::Left.step_wrapping(3, 0, 10, 10));
+        assert_eq!((0, 3), Orientation::Down.step_wrapping(9, 3, 10, 10));
+        assert_eq!((3, 0), Orientation::Right.step_wrapping(3, 9, 10, 10));
+        for o in Orientation::ALL {
+            assert_eq!(o.step(3, 3, 10, 10), Some(o.step_wrapping(3, 3, 10, 10)));
+        }
+        assert_eq!((0, 0), Orientation::Up.step_wrapping(0, 0, 1, 1));
+    }
+}
@@ -0,0 +1,199 @@
+use crate::parse::Line;
+use anyhow::{Result, anyhow};
+use std::ops::{Index, IndexMut};
+
+/// Runtime-sized 2D grid stored row-major, indexed by `(row, col)`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// build a grid by calling `f(row, col)` for each position
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let mut cells = Vec::with_capacity(rows * cols);
+        for rr in 0..rows {
+            for cc in 0..cols {
+                cells.push(f(rr, cc));
+            }
+        }
+
+        Self { rows, cols, cells }
+    }
+
+    /// parse one row per line and one cell per character, converting each with `f(row, col, char)`
+    ///
+    /// trailing blank lines are ignored; ragged rows are rejected
+    pub fn parse_with(
+        input_txt: &str,
+        mut f: impl FnMut(usize, usize, char) -> Result<T>,
+    ) -> Result<Self> {
+        let mut cells = Vec::new();
+        let mut cols: Option<usize> = None;
+        let mut rows = 0;
+
+        for (rr, text) in input_txt.trim_end_matches(['\n', '\r']).lines().enumerate() {
+            let line = Line {
+                number: rr + 1,
+                text: text.trim_end_matches('\r'),
+            };
+            let width = line.text.chars().count();
+            match cols {
+                None => cols = Some(width),
+                Some(c) if c != width => {
+                    return Err(line.error(line.text, format!("a row of {c} cells")).into());
+                }
+                Some(_) => {}
+            }
+
+            for (cc, ch) in line.text.chars().enumerate() {
+                cells.push(f(rr, cc, ch)?);
+            }
+            rows += 1;
+        }
+
+        match cols {
+            Some(cols) if cols > 0 => Ok(Self { rows, cols, cells }),
+            _ => Err(anyhow!("grid is empty")),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        if row < self.rows && col < self.cols {
+            self.cells.get(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if row < self.rows && col < self.cols {
+            self.cells.get_mut(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+
+    /// cells of a single row
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// every row in order, including the empty rows of a grid with no columns
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> + Clone {
+        (0..self.rows).map(move |row| self.row(row))
+    }
+
+    /// every cell in row-major order, with its `(row, col)`
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let cols = self.cols;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, cell)| ((idx / cols, idx % cols), cell))
+    }
+
+    /// flattened row-major view of every cell
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// rotate 90 degrees counter-clockwise, so the last column becomes the first row
+    pub fn rotate_ccw(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |rr, cc| {
+            self[(cc, self.cols - rr - 1)].clone()
+        })
+    }
+}
+
+impl Grid<char> {
+    /// parse a grid of characters
+    pub fn parse_chars(input_txt: &str) -> Result<Self> {
+        Self::parse_with(input_txt, |_, _, ch| Ok(ch))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({row}, {col}) out of bounds"
+        );
+        &self.cells[row * self.cols + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({row}, {col}) out of bounds"
+        );
+        &mut self.cells[row * self.cols + col]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::grid::*;
+
+    #[test]
+    fn test_parse() {
+        let g = Grid::parse_chars("abc\ndef\n").unwrap();
+        assert_eq!(2, g.rows());
+        assert_eq!(3, g.cols());
+        assert_eq!('a', g[(0, 0)]);
+        assert_eq!('f', g[(1, 2)]);
+        assert_eq!(None, g.get(2, 0));
+        assert_eq!(&['d', 'e', 'f'], g.row(1));
+
+        let g = Grid::parse_chars("ab\r\ncd\r\n").unwrap();
+        assert_eq!('d', g[(1, 1)]);
+
+        let e = Grid::parse_chars("abc\nde\n").unwrap_err();
+        let e = e.downcast_ref::<crate::parse::ParseError>().unwrap();
+        assert_eq!((2, "de"), (e.line, e.token.as_str()));
+        assert!(Grid::parse_chars("abc\n\ndef\n").is_err());
+        assert!(Grid::parse_chars("\n").is_err());
+    }
+
+    #[test]
+    fn test_rotate_ccw() {
+        let g = Grid::parse_chars("abc\ndef").unwrap();
+        let r = g.rotate_ccw();
+        assert_eq!(Grid::parse_chars("cf\nbe\nad").unwrap(), r);
+    }
+
+    #[test]
+    fn test_zero_width() {
+        let g = Grid::from_fn(2, 0, |_, _| 'x');
+        assert_eq!(vec![&[] as &[char]; 2], g.iter_rows().collect::<Vec<_>>());
+        assert_eq!(0, g.iter().count());
+        let r = g.rotate_ccw();
+        assert_eq!((0, 2), (r.rows(), r.cols()));
+        assert_eq!(0, r.iter_rows().count());
+    }
+}
@@ -0,0 +1,140 @@
+use anyhow::{Context, Result, anyhow};
+use clap::Parser;
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Where a day finds its puzzle input when no `--input` is given
+///
+/// build one with [`default_input!`](crate::default_input) so that the paths and the optional
+/// `embed-input` feature belong to the day crate rather than to `aoc-common`
+#[derive(Clone, Copy, Debug)]
+pub struct DefaultInput {
+    /// directory of the day crate, `input.txt` is looked up here
+    pub manifest_dir: &'static str,
+    /// `input.txt` compiled into the binary by the `embed-input` feature
+    pub embedded: Option<&'static str>,
+}
+
+impl DefaultInput {
+    /// default lookup path for the input file
+    pub fn path(&self) -> PathBuf {
+        Path::new(self.manifest_dir).join("input.txt")
+    }
+
+    /// load the puzzle input
+    ///
+    /// lookup order: `path` if given (`-` reads stdin), then the embedded input, then
+    /// `input.txt` in the day crate directory
+    pub fn load(&self, path: Option<&Path>) -> Result<String> {
+        if let Some(p) = path {
+            return read(p);
+        }
+
+        if let Some(txt) = self.embedded {
+            return Ok(txt.to_string());
+        }
+
+        let default_path = self.path();
+        if default_path.is_file() {
+            read(&default_path)
+        } else {
+            Err(anyhow!(
+                "no puzzle input at {}, pass one with --input <path> (or - for stdin)",
+                default_path.display()
+            ))
+        }
+    }
+}
+
+/// read the input from a file, or from stdin when `path` is `-`
+pub fn read(path: &Path) -> Result<String> {
+    if path == Path::new("-") {
+        let mut input_txt = String::new();
+        io::stdin()
+            .read_to_string(&mut input_txt)
+            .context("could not read input from stdin")?;
+        Ok(input_txt)
+    } else {
+        fs::read_to_string(path).with_context(|| format!("could not read {}", path.display()))
+    }
+}
+
+/// command line shared by the day binaries
+#[derive(Debug, Parser)]
+pub struct InputArgs {
+    /// puzzle input file, `-` to read stdin (defaults to the day's input.txt)
+    #[arg(short, long, value_name = "FILE")]
+    pub input: Option<PathBuf>,
+}
+
+impl InputArgs {
+    /// load the input named on the command line, falling back to `default`
+    pub fn load(&self, default: &DefaultInput) -> Result<String> {
+        default.load(self.input.as_deref())
+    }
+}
+
+/// load the input named by `-i/--input` on the process command line, falling back to `default`
+pub fn from_args(default: &DefaultInput) -> Result<String> {
+    InputArgs::parse().load(default)
+}
+
+/// [`DefaultInput`] for the calling crate
+///
+/// the calling crate decides whether `input.txt` is embedded with its own `embed-input` feature
+#[macro_export]
+macro_rules! default_input {
+    () => {
+        $crate::input::DefaultInput {
+            manifest_dir: env!("CARGO_MANIFEST_DIR"),
+            #[cfg(feature = "embed-input")]
+            embedded: Some(include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/input.txt"
+            ))),
+            #[cfg(not(feature = "embed-input"))]
+            embedded: None,
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::input::*;
+
+    #[test]
+    fn test_load() {
+        let embedded = DefaultInput {
+            manifest_dir: "/nonexistent",
+            embedded: Some("1 2\n"),
+        };
+        assert_eq!("1 2\n", embedded.load(None).unwrap());
+
+        let missing = DefaultInput {
+            manifest_dir: "/nonexistent",
+            embedded: None,
+        };
+        assert!(missing.load(None).is_err());
+        assert!(
+            missing
+                .load(Some(Path::new("/nonexistent/input.txt")))
+                .is_err()
+        );
+
+        // explicit path wins over embedded input
+        let manifest = DefaultInput {
+            manifest_dir: env!("CARGO_MANIFEST_DIR"),
+            embedded: Some("unused"),
+        };
+        let cargo_toml = manifest
+            .load(Some(
+                &Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"),
+            ))
+            .unwrap();
+        assert!(cargo_toml.contains("aoc-common"));
+    }
+}
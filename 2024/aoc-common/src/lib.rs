@@ -0,0 +1,12 @@
+//! Helpers shared by the day crates in the 2024 workspace
+//!
+//! New days should reach for these before copying input handling from an old day.
+
+pub mod direction;
+pub mod grid;
+pub mod input;
+pub mod parallel;
+pub mod parse;
+pub mod readme;
+pub mod report;
+pub mod solution;
@@ -0,0 +1,96 @@
+use std::{env, num::NonZeroUsize, thread};
+
+/// worker threads to use: `AOC_THREADS` if it is set to a positive number, otherwise one per core
+pub fn threads() -> usize {
+    env::var("AOC_THREADS")
+        .ok()
+        .and_then(|t| t.parse::<NonZeroUsize>().ok())
+        .or_else(|| thread::available_parallelism().ok())
+        .map_or(1, NonZeroUsize::get)
+}
+
+/// `items.iter().filter_map(f)` spread over `threads` scoped threads
+///
+/// each thread takes a contiguous chunk of `items` and the chunks are joined back in order, so the
+/// result is the same as the sequential version whatever the thread count
+pub fn filter_map<T, R>(items: &[T], threads: usize, f: impl Fn(&T) -> Option<R> + Sync) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    filter_map_with(items, threads, || (), |_, item| f(item))
+}
+
+/// like [`filter_map`], but each thread gets its own scratch state from `init` to pass to `f`
+pub fn filter_map_with<T, S, R>(
+    items: &[T],
+    threads: usize,
+    init: impl Fn() -> S + Sync,
+    f: impl Fn(&mut S, &T) -> Option<R> + Sync,
+) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    if threads <= 1 || items.len() <= 1 {
+        let mut state = init();
+        return items
+            .iter()
+            .filter_map(|item| f(&mut state, item))
+            .collect();
+    }
+
+    let chunk_len = items.len().div_ceil(threads);
+    let (init, f) = (&init, &f);
+    thread::scope(|s| {
+        let workers: Vec<_> = items
+            .chunks(chunk_len)
+            .map(|chunk| {
+                s.spawn(move || {
+                    let mut state = init();
+                    chunk
+                        .iter()
+                        .filter_map(|item| f(&mut state, item))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|w| w.join().expect("worker thread panicked"))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::parallel::*;
+
+    #[test]
+    fn test_filter_map() {
+        let items: Vec<u32> = (0..100).collect();
+        let odd_squares = |n: &u32| (n % 2 == 1).then_some(n * n);
+
+        let expected: Vec<u32> = items.iter().filter_map(odd_squares).collect();
+        for threads in [0, 1, 3, 8, 200] {
+            assert_eq!(expected, filter_map(&items, threads, odd_squares));
+        }
+        assert!(filter_map(&[] as &[u32], 4, odd_squares).is_empty());
+
+        // running total per thread, so only the chunk boundaries matter
+        let totals = filter_map_with(
+            &items,
+            4,
+            || 0,
+            |sum, n| {
+                *sum += n;
+                Some(*sum)
+            },
+        );
+        assert_eq!(totals[24], (0..25).sum());
+        assert_eq!(totals[25], 25);
+        assert!(threads() >= 1);
+    }
+}
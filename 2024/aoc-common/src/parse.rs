@@ -0,0 +1,221 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// Where and why the puzzle input could not be parsed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number in the input
+    pub line: usize,
+    /// 1-based column (in characters) where `token` starts
+    pub column: usize,
+    /// the offending text, empty when something is missing at the end of the line
+    pub token: String,
+    /// what should have been there, e.g. "a page number"
+    pub expected: String,
+    /// the whole line, to show `token` in context
+    pub text: String,
+}
+
+impl ParseError {
+    pub fn new(line: &Line, column: usize, token: &str, expected: impl Display) -> Self {
+        Self {
+            line: line.number,
+            column,
+            token: token.to_string(),
+            expected: expected.to_string(),
+            text: line.text.to_string(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.token.is_empty() {
+            write!(f, "nothing")?;
+        } else {
+            write!(f, "{:?}", self.token)?;
+        }
+        write!(f, " in {:?}", self.text)
+    }
+}
+
+impl Error for ParseError {}
+
+/// a line of the input with its 1-based line number, to report where parsing failed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    /// the line without its line ending
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// 1-based column of `token`, which should be a slice of `text` (or empty for the end of the line)
+    fn column(&self, token: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&o| o <= self.text.len())
+            .or_else(|| self.text.find(token))
+            .unwrap_or(self.text.len());
+        self.text[..offset].chars().count() + 1
+    }
+
+    /// an error pointing at `token` in this line
+    pub fn error(&self, token: &str, expected: impl Display) -> ParseError {
+        ParseError::new(self, self.column(token), token, expected)
+    }
+
+    /// parse a single token of this line, ignoring surrounding whitespace
+    pub fn field<T: FromStr>(&self, token: &str, expected: &str) -> Result<T, ParseError> {
+        let token = token.trim();
+        token.parse::<T>().map_err(|_| self.error(token, expected))
+    }
+
+    /// parse every whitespace-separated field of the line
+    pub fn fields<T: FromStr>(&self, expected: &str) -> Result<Vec<T>, ParseError> {
+        self.text
+            .split_ascii_whitespace()
+            .map(|token| self.field(token, expected))
+            .collect()
+    }
+
+    /// parse every `sep`-separated field of the line, ignoring whitespace around each field
+    pub fn split_fields<T: FromStr>(
+        &self,
+        sep: &str,
+        expected: &str,
+    ) -> Result<Vec<T>, ParseError> {
+        self.text
+            .split(sep)
+            .map(|token| self.field(token, expected))
+            .collect()
+    }
+
+    /// split the line around the first `sep`
+    pub fn split_once(&self, sep: &str) -> Result<(&'a str, &'a str), ParseError> {
+        self.text
+            .split_once(sep)
+            .ok_or_else(|| self.error(&self.text[self.text.len()..], format!("{sep:?}")))
+    }
+}
+
+/// numbered non-blank lines of the input
+///
+/// `\r\n` line endings and stray `\r`s at the end of a line are dropped
+pub fn lines(input_txt: &str) -> impl Iterator<Item = Line<'_>> {
+    input_txt
+        .lines()
+        .enumerate()
+        .map(|(idx, text)| Line {
+            number: idx + 1,
+            text: text.trim_end_matches('\r'),
+        })
+        .filter(|l| !l.text.trim().is_empty())
+}
+
+/// split the input into sections of numbered lines, separated by one or more blank lines
+pub fn sections(input_txt: &str) -> Vec<Vec<Line<'_>>> {
+    let mut result = Vec::new();
+    let mut section = Vec::new();
+    let mut next_number = 1;
+
+    for line in lines(input_txt) {
+        if line.number != next_number && !section.is_empty() {
+            result.push(std::mem::take(&mut section));
+        }
+        next_number = line.number + 1;
+        section.push(line);
+    }
+
+    if !section.is_empty() {
+        result.push(section);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::parse::*;
+
+    fn line(text: &str) -> Line<'_> {
+        Line { number: 3, text }
+    }
+
+    #[test]
+    fn test_fields() {
+        assert_eq!(vec![3, 4], line("3   4").fields::<u32>("a number").unwrap());
+        assert_eq!(
+            vec![47, 53],
+            line("47|53").split_fields::<u8>("|", "a page").unwrap()
+        );
+        assert_eq!(
+            vec![75, 47, 61],
+            line("75,47,61\r")
+                .split_fields::<u8>(",", "a page")
+                .unwrap()
+        );
+        assert_eq!(
+            21037,
+            line("21037 ").field::<u64>("21037 ", "a number").unwrap()
+        );
+
+        let e = line("1 x 3").fields::<u8>("a level").unwrap_err();
+        assert_eq!((3, 3, "x"), (e.line, e.column, e.token.as_str()));
+        assert_eq!(
+            "line 3, column 3: expected a level, found \"x\" in \"1 x 3\"",
+            e.to_string()
+        );
+
+        let e = line("75,,61")
+            .split_fields::<u8>(",", "a page")
+            .unwrap_err();
+        assert_eq!((4, ""), (e.column, e.token.as_str()));
+        assert!(e.to_string().contains("found nothing"));
+
+        let e = line("190 10 19").split_once(": ").unwrap_err();
+        assert_eq!(10, e.column);
+        assert_eq!(
+            ("190", "10 19"),
+            line("190: 10 19").split_once(": ").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        let numbered: Vec<_> = lines("a\r\n\nb\r\n\n")
+            .map(|l| (l.number, l.text))
+            .collect();
+        assert_eq!(vec![(1, "a"), (3, "b")], numbered);
+    }
+
+    #[test]
+    fn test_sections() {
+        let texts = |input| -> Vec<Vec<&str>> {
+            sections(input)
+                .into_iter()
+                .map(|s| s.into_iter().map(|l| l.text).collect())
+                .collect()
+        };
+        assert_eq!(
+            vec![vec!["1|2", "3|4"], vec!["1,2"]],
+            texts("1|2\n3|4\n\n1,2\n")
+        );
+        assert_eq!(
+            vec![vec!["1|2", "3|4"], vec!["1,2"]],
+            texts("\r\n1|2\r\n3|4\r\n\r\n\r\n1,2")
+        );
+        assert!(sections("\n\n").is_empty());
+        assert_eq!(6, sections("1|2\n3|4\n\n\n\n1,2\n")[1][0].number);
+    }
+}
@@ -0,0 +1,168 @@
+//! Examples and their answers pulled out of a day's README (the puzzle text)
+//!
+//! the puzzle text is pasted from the website, so an example is the block of lines after a line
+//! ending in e.g. `For example:` (and a blank line), up to the next line of prose. The
+//! answer to an example is the number that follows a phrase such as `a total distance of`.
+
+use crate::solution::Solution;
+use anyhow::{Result, anyhow};
+
+/// true for a line of the puzzle's prose rather than of an example
+fn is_prose(line: &str) -> bool {
+    line.split_whitespace()
+        .filter(|w| {
+            let w = w.trim_end_matches([',', '.', ';', ':', '!', '?']);
+            w.len() > 1 && w.chars().all(|c| c.is_alphabetic() || c == '\'')
+        })
+        .count()
+        >= 3
+}
+
+/// the rest of the README after the first line ending in `intro`, and the example that line introduces
+fn find_example<'a>(readme: &'a str, intro: &str) -> Result<(&'a str, String)> {
+    let mut offset = 0;
+    let mut found = false;
+    for line in readme.split_inclusive('\n') {
+        offset += line.len();
+        if line.trim_end().ends_with(intro) {
+            found = true;
+            break;
+        }
+    }
+    if !found {
+        return Err(anyhow!("no line ending in {intro:?}"));
+    }
+
+    let rest = &readme[offset..];
+    let mut example = String::new();
+    for line in rest.lines().skip_while(|l| l.trim().is_empty()) {
+        let line = line.trim_end();
+        if is_prose(line) {
+            break;
+        }
+        example.push_str(line);
+        example.push('\n');
+    }
+    // sections of an example are separated by a blank line, but nothing follows the last one
+    example.truncate(example.trim_end().len());
+    if !example.is_empty() {
+        example.push('\n');
+    }
+
+    if example.is_empty() {
+        Err(anyhow!("no example after {intro:?}"))
+    } else {
+        Ok((rest, example))
+    }
+}
+
+/// the example introduced by the first line ending in `intro`
+pub fn example(readme: &str, intro: &str) -> Result<String> {
+    Ok(find_example(readme, intro)?.1)
+}
+
+/// the number right after the first `phrase` in `text`
+pub fn answer<'a>(text: &'a str, phrase: &str) -> Result<&'a str> {
+    let (_, rest) = text
+        .split_once(phrase)
+        .ok_or_else(|| anyhow!("no {phrase:?} in the README"))?;
+    let rest = rest.trim_start();
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    if end == 0 {
+        return Err(anyhow!("no number after {phrase:?}"));
+    }
+    Ok(&rest[..end])
+}
+
+/// solve the example introduced by `intro` with `solve` and compare it to the answer after `phrase`
+///
+/// the answer is looked for after `intro`, so that `phrase` only has to be unique from there on
+pub fn check_example<S: Solution>(
+    readme: &str,
+    intro: &str,
+    phrase: &str,
+    solve: impl Fn(&S::Input) -> Result<String>,
+) -> Result<()> {
+    let (rest, example) = find_example(readme, intro)?;
+    let expected = answer(rest, phrase)?;
+
+    let computed = solve(&S::parse(&example)?)?;
+    if computed == expected {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "example after {intro:?} gave {computed}, README says {phrase:?} {expected}"
+        ))
+    }
+}
+
+/// generate a `#[test]` for each example in a day's README
+///
+/// ```ignore
+/// aoc_common::readme_tests! {
+///     Day1, include_str!("../README.md");
+///     test_readme_1: part1 "For example:" => "a total distance of";
+///     test_readme_2: part2 "Here are the same example lists again:" => "process is";
+/// }
+/// ```
+#[macro_export]
+macro_rules! readme_tests {
+    ($solution:ty, $readme:expr; $($name:ident: $part:ident $intro:literal => $phrase:literal;)+) => {
+        $(
+            #[test]
+            fn $name() {
+                $crate::readme::check_example::<$solution>($readme, $intro, $phrase, |input| {
+                    <$solution as $crate::solution::Solution>::$part(input).map(|a| a.to_string())
+                })
+                .unwrap();
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::readme::*;
+
+    const README: &str = "```text\n\
+        For example:\n\
+        \n\
+        3   4\n\
+        4   3\n\
+        Maybe the lists are only off by a small amount!\n\
+        \n\
+        In the example above, this is 2 + 1, a total distance of 11!\n\
+        Here is another example:\n\
+        \n\
+        ..#..\n\
+        #.^..\n\
+        \n\
+        .....\n\
+        \n\
+        Here is nothing:\n\
+        \n\
+        This has no example at all.\n\
+        ```\n";
+
+    #[test]
+    fn test_example() {
+        assert_eq!("3   4\n4   3\n", example(README, "For example:").unwrap());
+        assert_eq!(
+            "..#..\n#.^..\n\n.....\n",
+            example(README, "another example:").unwrap()
+        );
+        assert!(example(README, "Here is nothing:").is_err());
+        assert!(example(README, "missing:").is_err());
+    }
+
+    #[test]
+    fn test_answer() {
+        assert_eq!("11", answer(README, "a total distance of").unwrap());
+        assert!(answer(README, "this is 2 +").is_ok());
+        assert!(answer(README, "In the example").is_err());
+        assert!(answer(README, "not there").is_err());
+    }
+}
@@ -0,0 +1,14 @@
+use anyhow::Result;
+use std::process::ExitCode;
+
+/// turn the result of a binary's `run` into its exit status, printing any error as a one-line
+/// diagnostic (with its context) instead of `main`'s debug dump and backtrace
+pub fn exit_code(result: Result<()>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use anyhow::Result;
+use std::fmt::Display;
+
+/// Common shape of every day: parse the input once, then solve each part from the parsed input
+///
+/// ```ignore
+/// pub struct Day1;
+///
+/// impl Solution for Day1 {
+///     type Input = [Vec<u32>; 2];
+///     type Answer1 = u32;
+///     type Answer2 = u32;
+///     ...
+/// }
+/// ```
+pub trait Solution {
+    /// parsed puzzle input shared by both parts
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input_txt: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+}
@@ -0,0 +1,235 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// an answer as written in the answers file
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// what is known about the answer to one part, from submitting it to adventofcode.com
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct PartRecord {
+    /// accepted answer
+    pub answer: Option<Answer>,
+    /// `answer` is only what our code printed, it hasn't been submitted and accepted yet
+    pub unconfirmed: bool,
+    /// guesses that were rejected as too low
+    pub too_low: Vec<i64>,
+    /// guesses that were rejected as too high
+    pub too_high: Vec<i64>,
+    /// guesses that were rejected without a hint
+    pub wrong: Vec<Answer>,
+}
+
+/// result of checking a computed answer against its record
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    /// matches the accepted answer
+    Correct,
+    /// matches a recorded answer that adventofcode.com hasn't confirmed
+    Unchanged,
+    /// differs from the recorded answer
+    Changed { expected: Answer },
+    /// one of the rejected guesses
+    KnownWrong,
+    /// not above the highest guess that was too low
+    TooLow { bound: i64 },
+    /// not below the lowest guess that was too high
+    TooHigh { bound: i64 },
+    /// nothing recorded contradicts it, but nothing confirms it either
+    Unverified,
+}
+
+impl Verdict {
+    /// true when the computed answer is known to be wrong
+    pub fn is_failure(&self) -> bool {
+        !matches!(
+            self,
+            Verdict::Correct | Verdict::Unchanged | Verdict::Unverified
+        )
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Unchanged => write!(f, "unchanged (unconfirmed)"),
+            Verdict::Changed { expected } => write!(f, "CHANGED (expected {expected})"),
+            Verdict::KnownWrong => write!(f, "KNOWN WRONG"),
+            Verdict::TooLow { bound } => write!(f, "TOO LOW (must be > {bound})"),
+            Verdict::TooHigh { bound } => write!(f, "TOO HIGH (must be < {bound})"),
+            Verdict::Unverified => write!(f, "unverified"),
+        }
+    }
+}
+
+impl PartRecord {
+    /// check a computed answer (as displayed) against everything recorded for this part
+    pub fn check(&self, computed: &str) -> Verdict {
+        if self.wrong.iter().any(|w| w.to_string() == computed) {
+            return Verdict::KnownWrong;
+        }
+
+        let computed_int = computed.parse::<i64>().ok();
+        if let Some(n) = computed_int {
+            if self.too_low.contains(&n) || self.too_high.contains(&n) {
+                return Verdict::KnownWrong;
+            }
+            if let Some(&bound) = self.too_low.iter().max().filter(|&&b| n <= b) {
+                return Verdict::TooLow { bound };
+            }
+            if let Some(&bound) = self.too_high.iter().min().filter(|&&b| n >= b) {
+                return Verdict::TooHigh { bound };
+            }
+        }
+
+        match &self.answer {
+            Some(expected) if expected.to_string() == computed && self.unconfirmed => {
+                Verdict::Unchanged
+            }
+            Some(expected) if expected.to_string() == computed => Verdict::Correct,
+            Some(expected) => Verdict::Changed {
+                expected: expected.clone(),
+            },
+            None => Verdict::Unverified,
+        }
+    }
+}
+
+/// every record for one year, keyed by `dayN` then `partN`
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, BTreeMap<String, PartRecord>>);
+
+impl Answers {
+    /// `<year>/answers.toml` at the top of the repository
+    pub fn path(year: u16) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../..")
+            .join(year.to_string())
+            .join("answers.toml")
+    }
+
+    pub fn parse(answers_txt: &str) -> Result<Self> {
+        Ok(toml::from_str(answers_txt)?)
+    }
+
+    /// load the answers for a year, or no answers if the year doesn't have a file yet
+    pub fn load(year: u16) -> Result<Self> {
+        let path = Self::path(year);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+
+        let answers_txt = fs::read_to_string(&path)
+            .with_context(|| format!("could not read {}", path.display()))?;
+        Self::parse(&answers_txt).with_context(|| format!("could not parse {}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&PartRecord> {
+        self.0
+            .get(&format!("day{day}"))?
+            .get(&format!("part{part}"))
+    }
+
+    /// check a computed answer, anything without a record is unverified
+    pub fn check(&self, day: u8, part: u8, computed: &str) -> Verdict {
+        self.get(day, part)
+            .map_or(Verdict::Unverified, |r| r.check(computed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::answers::*;
+
+    const ANSWERS_TOML: &str = r#"
+        [day4.part1]
+        answer = 2593
+        too_high = [2750]
+
+        [day4.part2]
+        too_low = [1916]
+        wrong = [1968]
+
+        [day6.part2]
+        too_low = [931]
+        too_high = [1656]
+
+        [day9.part1]
+        answer = "xyz"
+
+        [day9.part2]
+        answer = 7
+        unconfirmed = true
+    "#;
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse(ANSWERS_TOML).unwrap();
+
+        assert_eq!(Verdict::Correct, answers.check(4, 1, "2593"));
+        assert_eq!(
+            Verdict::Changed {
+                expected: Answer::Int(2593)
+            },
+            answers.check(4, 1, "2594")
+        );
+        assert_eq!(
+            Verdict::TooHigh { bound: 2750 },
+            answers.check(4, 1, "2751")
+        );
+        assert_eq!(Verdict::KnownWrong, answers.check(4, 1, "2750"));
+
+        assert_eq!(Verdict::KnownWrong, answers.check(4, 2, "1968"));
+        assert_eq!(Verdict::KnownWrong, answers.check(4, 2, "1916"));
+        assert_eq!(Verdict::TooLow { bound: 1916 }, answers.check(4, 2, "1900"));
+        assert_eq!(Verdict::Unverified, answers.check(4, 2, "1950"));
+
+        assert_eq!(Verdict::Unverified, answers.check(6, 2, "1655"));
+        assert_eq!(Verdict::TooLow { bound: 931 }, answers.check(6, 2, "12"));
+
+        assert_eq!(Verdict::Correct, answers.check(9, 1, "xyz"));
+        assert_eq!(Verdict::Unverified, answers.check(1, 1, "11"));
+
+        // still catches a change, but isn't known to be right
+        assert_eq!(Verdict::Unchanged, answers.check(9, 2, "7"));
+        assert!(!Verdict::Unchanged.is_failure());
+        assert_eq!(
+            Verdict::Changed {
+                expected: Answer::Int(7)
+            },
+            answers.check(9, 2, "8")
+        );
+
+        assert!(Answers::parse("[day1.part1]\nanwser = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_load() {
+        assert!(Answers::load(2020).unwrap().get(1, 2).is_some());
+        assert!(Answers::load(2024).unwrap().get(6, 2).is_some());
+        assert_eq!(Answers::default(), Answers::load(1999).unwrap());
+    }
+}
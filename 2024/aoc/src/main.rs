@@ -0,0 +1,154 @@
+//! Advent of Code runner: solve any registered year/day/part with consistent output and timings
+//!
+//! ```sh
+//! % cargo run -- run 2024 6 --part 2
+//! % cargo run -- run --all
+//! % cargo run -- run 2024 6 --input colleague.txt
+//! ```
+//!
+//! answers to the default inputs are checked against `<year>/answers.toml`
+
+mod answers;
+mod registry;
+
+use answers::Answers;
+use anyhow::{Result, anyhow};
+use clap::{Args, Parser, Subcommand};
+use std::{collections::BTreeMap, path::PathBuf, process::ExitCode, time::Duration};
+
+#[derive(Parser)]
+#[command(name = "aoc", version, about = "Advent of Code runner")]
+struct Cli {
+    /// defaults to `run --all`
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// solve a single day, every day in a year, or every day
+    Run(RunArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// puzzle year, e.g. 2024
+    #[arg(required_unless_present = "all")]
+    year: Option<u16>,
+
+    /// puzzle day (every day in the year when omitted)
+    day: Option<u8>,
+
+    /// only solve this part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// solve every registered day
+    #[arg(long, conflicts_with_all = ["year", "day"])]
+    all: bool,
+
+    /// puzzle input file, `-` to read stdin (defaults to the day's input.txt)
+    #[arg(short, long, value_name = "FILE")]
+    input: Option<PathBuf>,
+}
+
+impl RunArgs {
+    fn all() -> Self {
+        Self {
+            year: None,
+            day: None,
+            part: None,
+            all: true,
+            input: None,
+        }
+    }
+}
+
+fn run(args: &RunArgs) -> Result<()> {
+    let days: Vec<_> = registry::select(args.year, args.day).collect();
+    if days.is_empty() {
+        return Err(match (args.year, args.day) {
+            (Some(y), Some(d)) => anyhow!("no solution registered for {y} day {d}"),
+            (Some(y), None) => anyhow!("no solutions registered for {y}"),
+            _ => anyhow!("no solutions registered"),
+        });
+    }
+    if args.input.is_some() && days.len() > 1 {
+        return Err(anyhow!("--input needs a single YEAR and DAY"));
+    }
+
+    // answers are only known for our own inputs
+    let check = args.input.is_none();
+    let mut answers: BTreeMap<u16, Answers> = BTreeMap::new();
+
+    let mut failures = 0;
+    let mut total = Duration::ZERO;
+    for day in days {
+        if check && !answers.contains_key(&day.year) {
+            answers.insert(day.year, Answers::load(day.year)?);
+        }
+
+        let label = format!("{} day {:>2}", day.year, day.day);
+
+        let solved = day
+            .input
+            .load(args.input.as_deref())
+            .and_then(|input_txt| (day.solve)(&input_txt, args.part));
+        let solved = match solved {
+            Ok(solved) => solved,
+            Err(e) => {
+                failures += 1;
+                println!("{label}: error: {e:#}");
+                continue;
+            }
+        };
+
+        total += solved.parse_elapsed;
+        println!(
+            "{:<40} {:>12.3?}",
+            format!("{label} parse:"),
+            solved.parse_elapsed
+        );
+
+        for part in solved.parts {
+            total += part.elapsed;
+
+            let (answer, verdict) = match part.answer {
+                Ok(answer) => {
+                    let verdict = answers
+                        .get(&day.year)
+                        .map(|a| a.check(day.day, part.part, &answer));
+                    if verdict.as_ref().is_some_and(|v| v.is_failure()) {
+                        failures += 1;
+                    }
+                    (answer, verdict)
+                }
+                Err(e) => {
+                    failures += 1;
+                    (format!("error: {e:#}"), None)
+                }
+            };
+            let verdict = verdict.map_or_else(String::new, |v| format!("  {v}"));
+            println!(
+                "{label} part {}: {answer:<20} {:>12.3?}{verdict}",
+                part.part, part.elapsed
+            );
+        }
+    }
+    println!("{:<40} {total:>12.3?}", "total");
+
+    if failures > 0 {
+        return Err(anyhow!("{failures} error(s) or wrong answer(s)"));
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    aoc_common::report::exit_code(match cli.command {
+        Some(Command::Run(args)) => run(&args),
+        None => run(&RunArgs::all()),
+    })
+}
@@ -0,0 +1,124 @@
+use anyhow::Result;
+use aoc_common::{input::DefaultInput, solution::Solution};
+use std::time::{Duration, Instant};
+
+/// answer to one part, with how long it took to solve
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Result<String>,
+    pub elapsed: Duration,
+}
+
+/// a parsed input and the parts solved from it
+pub struct Solved {
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartResult>,
+}
+
+/// everything the runner needs to solve one day
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub input: DefaultInput,
+    /// parse the input and solve the requested part (or both)
+    pub solve: fn(&str, Option<u8>) -> Result<Solved>,
+}
+
+/// parse once, then solve and time each requested part of `S`
+fn solve<S: Solution>(input_txt: &str, part: Option<u8>) -> Result<Solved> {
+    let start = Instant::now();
+    let input = S::parse(input_txt)?;
+    let parse_elapsed = start.elapsed();
+
+    let mut parts = Vec::new();
+    if part.is_none_or(|p| p == 1) {
+        let start = Instant::now();
+        let answer = S::part1(&input).map(|a| a.to_string());
+        parts.push(PartResult {
+            part: 1,
+            answer,
+            elapsed: start.elapsed(),
+        });
+    }
+    if part.is_none_or(|p| p == 2) {
+        let start = Instant::now();
+        let answer = S::part2(&input).map(|a| a.to_string());
+        parts.push(PartResult {
+            part: 2,
+            answer,
+            elapsed: start.elapsed(),
+        });
+    }
+
+    Ok(Solved {
+        parse_elapsed,
+        parts,
+    })
+}
+
+/// helper macro to register a day crate that exposes `INPUT` and a `Solution`
+macro_rules! day {
+    ($year:literal, $day:literal, $krate:ident :: $solution:ident) => {
+        Day {
+            year: $year,
+            day: $day,
+            input: $krate::INPUT,
+            solve: solve::<$krate::$solution>,
+        }
+    };
+}
+
+/// every day known to the runner, in (year, day) order
+pub const DAYS: &[Day] = &[
+    day!(2020, 1, aoc2020_day1::Day1),
+    day!(2024, 1, day1::Day1),
+    day!(2024, 2, day2::Day2),
+    day!(2024, 3, day3::Day3),
+    day!(2024, 4, day4::Day4),
+    day!(2024, 5, day5::Day5),
+    day!(2024, 6, day6::Day6),
+    day!(2024, 7, day7::Day7),
+];
+
+/// days matching the optional year and day filters
+pub fn select(year: Option<u16>, day: Option<u8>) -> impl Iterator<Item = &'static Day> {
+    DAYS.iter()
+        .filter(move |d| year.is_none_or(|y| y == d.year))
+        .filter(move |d| day.is_none_or(|n| n == d.day))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::registry::*;
+
+    #[test]
+    fn test_select() {
+        assert_eq!(7, select(Some(2024), None).count());
+        assert_eq!(DAYS.len(), select(None, None).count());
+        assert_eq!(1, select(Some(2020), None).count());
+        assert_eq!(0, select(Some(2023), None).count());
+
+        let day6: Vec<_> = select(Some(2024), Some(6)).collect();
+        assert_eq!(1, day6.len());
+        assert_eq!((2024, 6), (day6[0].year, day6[0].day));
+    }
+
+    #[test]
+    fn test_solve() {
+        let solved =
+            solve::<day1::Day1>("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n", None).unwrap();
+        let answers: Vec<_> = solved
+            .parts
+            .into_iter()
+            .map(|p| (p.part, p.answer.unwrap()))
+            .collect();
+        assert_eq!(vec![(1, "11".to_string()), (2, "31".to_string())], answers);
+
+        let solved = solve::<day1::Day1>("3   4\n", Some(2)).unwrap();
+        assert_eq!(1, solved.parts.len());
+        assert_eq!(2, solved.parts[0].part);
+
+        assert!(solve::<day1::Day1>("3 4 5\n", None).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+#![doc = include_str!("../README.md")]
+
+use anyhow::Result;
+use aoc_common::{input::DefaultInput, parse, solution::Solution};
+
+/// where to find the puzzle input by default
+pub const INPUT: DefaultInput = aoc_common::default_input!();
+
+/// parse data and store in one sorted list per column
+fn parse_lists(input_txt: &str) -> Result<[Vec<u32>; 2]> {
+    let mut list: [Vec<u32>; 2] = [vec![], vec![]];
+    for l in parse::lines(input_txt) {
+        match l.fields::<u32>("a location ID")?[..] {
+            [left, right] => {
+                list[0].push(left);
+                list[1].push(right);
+            }
+            _ => return Err(l.error(l.text, "two location IDs").into()),
+        }
+    }
+
+    // sort each list (as per instructions)
+    list[0].sort();
+    list[1].sort();
+
+    Ok(list)
+}
+
+fn total_distance(ary: &[Vec<u32>; 2]) -> Result<u32> {
+    // compute the sum of the absolute difference between each pair
+    let mut total_distance: u32 = 0;
+    for (left, right) in ary[0].iter().zip(ary[1].iter()) {
+        let distance = right.abs_diff(*left);
+
+        total_distance += distance;
+    }
+
+    Ok(total_distance)
+}
+
+fn total_similarity(ary: &[Vec<u32>; 2]) -> Result<u32> {
+    assert!(ary[1].is_sorted());
+
+    // compute the sum of the absolute difference between each pair
+    let mut similarity_sum: u32 = 0;
+    for left_val in ary[0].iter().copied() {
+        // brute-force: count instances of left_val in ary[1]
+        let mut count: usize = 0;
+        for right_item in ary[1].iter() {
+            // short-circuit since we have asserted that ary[1] is sorted
+            if *right_item > left_val {
+                break;
+            }
+
+            if left_val == *right_item {
+                count += 1;
+            }
+        }
+
+        let similarity = left_val * (count as u32);
+
+        similarity_sum += similarity;
+    }
+
+    Ok(similarity_sum)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = [Vec<u32>; 2];
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input_txt: &str) -> Result<Self::Input> {
+        parse_lists(input_txt)
+    }
+
+    /// total distance between the sorted lists
+    fn part1(input: &Self::Input) -> Result<u32> {
+        total_distance(input)
+    }
+
+    /// total similarity score between the lists
+    fn part2(input: &Self::Input) -> Result<u32> {
+        total_similarity(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    aoc_common::readme_tests! {
+        Day1, include_str!("../README.md");
+        test_1: part1 "For example:" => "a total distance of";
+        test_2: part2 "Here are the same example lists again:" => "at the end of this process is";
+    }
+
+    #[test]
+    fn test_parse() {
+        // pasted with CRLF line endings and a trailing blank line
+        let lists = Day1::parse("3   4\r\n4   3\r\n\r\n").unwrap();
+        assert_eq!([vec![3, 4], vec![3, 4]], lists);
+
+        let e = Day1::parse("3   4\n4   x\n").unwrap_err();
+        let e = e.downcast_ref::<aoc_common::parse::ParseError>().unwrap();
+        assert_eq!((2, 5, "x"), (e.line, e.column, e.token.as_str()));
+
+        assert!(Day1::parse("3   4   5\n").is_err());
+    }
+}
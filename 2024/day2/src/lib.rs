@@ -0,0 +1,102 @@
+#![doc = include_str!("../README.md")]
+
+use anyhow::Result;
+use aoc_common::{input::DefaultInput, parse, solution::Solution};
+use std::cmp::Ordering;
+
+/// where to find the puzzle input by default
+pub const INPUT: DefaultInput = aoc_common::default_input!();
+
+fn is_safe(levels: &[u8]) -> Result<bool> {
+    // check for sorted or reverse sorted
+    if levels.is_sorted() || levels.is_sorted_by(|a, b| a.cmp(b) == Ordering::Greater) {
+        // println!("{levels:?} is sorted");
+
+        // use overlapping sliding window to check distance of adjacent elements
+        for item in levels.windows(2) {
+            let first = item.first().unwrap();
+            let last = item.last().unwrap();
+
+            let diff = first.abs_diff(*last);
+            // print!("  {first}.abs_diff({last}) = {diff}\n");
+
+            if (1..=3).contains(&diff) {
+                // println!("    safe")
+            } else {
+                // println!("    UNSAFE");
+                return Ok(false);
+            }
+        }
+    } else {
+        // println!("{levels:?} is NOT sorted");
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+fn is_safe_dampened(levels: &[u8]) -> Result<bool> {
+    // brute-force: short-circuiting, recheck after removing each element
+    for idx in 0..levels.len() {
+        let mut lclone = levels.to_vec();
+        let _discard = lclone.remove(idx);
+
+        if is_safe(&lclone).unwrap() {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    /// one list of levels per report
+    type Input = Vec<Vec<u8>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input_txt: &str) -> Result<Self::Input> {
+        parse::lines(input_txt)
+            .map(|l| Ok(l.fields("a level")?))
+            .collect()
+    }
+
+    /// count of safe reports
+    fn part1(input: &Self::Input) -> Result<usize> {
+        let mut part1_count: usize = 0;
+        for levels in input {
+            if is_safe(levels)? {
+                part1_count += 1;
+            }
+        }
+
+        Ok(part1_count)
+    }
+
+    /// count of safe reports when the Problem Dampener can remove a single level
+    fn part2(input: &Self::Input) -> Result<usize> {
+        let mut part2_count: usize = 0;
+        for levels in input {
+            // only do the more expensive recheck on unsafe reports
+            if is_safe(levels)? || is_safe_dampened(levels)? {
+                part2_count += 1;
+            }
+        }
+
+        Ok(part2_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    aoc_common::readme_tests! {
+        Day2, include_str!("../README.md");
+        test_1: part1 "For example:" => "So, in this example,";
+        test_2: part2 "For example:" => "Thanks to the Problem Dampener,";
+    }
+}
@@ -0,0 +1,85 @@
+#![doc = include_str!("../README.md")]
+
+use anyhow::Result;
+use aoc_common::{input::DefaultInput, solution::Solution};
+use regex::Regex;
+
+/// where to find the puzzle input by default
+pub const INPUT: DefaultInput = aoc_common::default_input!();
+
+/// sum of all uncorrupted `mul` instructions
+fn sum_of_products(input: &str) -> Result<u32> {
+    let mut part1_sum: u32 = 0;
+
+    let part1_re = Regex::new(r"(?m)mul\((?P<m1>\d+),(?P<m2>\d+)\)").unwrap();
+
+    for mat in part1_re.captures_iter(input) {
+        // dbg!(&mat);
+        let prod = mat["m1"].parse::<u32>().unwrap() * mat["m2"].parse::<u32>().unwrap();
+        // dbg!(&prod);
+        part1_sum += prod;
+    }
+    Ok(part1_sum)
+}
+
+/// sum of the `mul` instructions enabled by `do()`/`don't()`
+fn sum_of_enabled_products(input: &str) -> Result<u32> {
+    let mut part2_sum: u32 = 0;
+
+    let part2_re =
+        Regex::new(r"(?m)mul\((?P<m1>\d+),(?P<m2>\d+)\)|(?P<enable>do\(\))|(?P<disable>don't\(\))")
+            .unwrap();
+
+    let mut enabled: bool = true;
+    for mat in part2_re.captures_iter(input) {
+        // dbg!(&mat);
+
+        // TODO: can a regex::Capture be destructured in a match-block?
+        // update state based on which which capture group matched
+        if mat.name("disable").is_some() {
+            enabled = false;
+        } else if mat.name("enable").is_some() {
+            enabled = true;
+        } else {
+            if enabled {
+                let prod = mat["m1"].parse::<u32>().unwrap() * mat["m2"].parse::<u32>().unwrap();
+                // dbg!(&prod);
+                part2_sum += prod;
+            }
+        }
+    }
+    Ok(part2_sum)
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    /// corrupted memory is scanned as-is
+    type Input = String;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input_txt: &str) -> Result<Self::Input> {
+        Ok(input_txt.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<u32> {
+        sum_of_products(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u32> {
+        sum_of_enabled_products(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    aoc_common::readme_tests! {
+        Day3, include_str!("../README.md");
+        test_1: part1 "consider the following section of corrupted memory:" => "produces";
+        test_2: part2 "For example:" => "the sum of the results is";
+    }
+}
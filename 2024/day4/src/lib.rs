@@ -0,0 +1,243 @@
+#![doc = include_str!("../README.md")]
+
+use anyhow::Result;
+use aoc_common::{grid::Grid, input::DefaultInput, solution::Solution};
+use regex::Regex;
+
+/// where to find the puzzle input by default
+pub const INPUT: DefaultInput = aoc_common::default_input!();
+
+// struct Blocky<T, const ROWS: usize, const COLS: usize>(pub [[Option<T>; COLS]; ROWS]);
+
+// struct BlockyChar140(pub Blocky<char, 140, 140>);
+
+// impl Iterator for BlockyChar140 {
+//     type Item = [[char; 3]; 3];
+
+//     fn next(&mut self) -> Option<Self::Item> {
+
+//         let ary2d = &self.0.0;
+
+//         let curr_row = ary2d.into_iter().next();
+
+//         let curr_item = self.0.0.into_iter().next().unwrap().into_iter().next();
+
+//         if curr_item.is_none() {
+//             return None;
+//         }
+//         let temp_item = curr_item.clone();
+
+//         let result = [['A'; 3];3];
+//         Some(result)
+//     }
+// }
+
+/// every 3x3 block of the grid, by its top-left corner; none when the grid is narrower than 3
+fn foreach3x3(ary: &Grid<char>) -> Result<Grid<[[char; 3]; 3]>> {
+    let rows = ary.rows().saturating_sub(2);
+    let cols = ary.cols().saturating_sub(2);
+    let result = Grid::from_fn(rows, cols, |row, col| {
+        [
+            [ary[(row, col)], ary[(row, col + 1)], ary[(row, col + 2)]],
+            [
+                ary[(row + 1, col)],
+                ary[(row + 1, col + 1)],
+                ary[(row + 1, col + 2)],
+            ],
+            [
+                ary[(row + 2, col)],
+                ary[(row + 2, col + 1)],
+                ary[(row + 2, col + 2)],
+            ],
+        ]
+    });
+
+    Ok(result)
+}
+
+fn seach_for_xmas<'a>(rows: impl IntoIterator<Item = &'a [char]> + Clone) -> Result<usize> {
+    let mut part1_count: usize = 0;
+
+    // count forward and reverse
+    let re = Regex::new(r"XMAS|SAMX").unwrap();
+    for row in rows.clone() {
+        let tmp = String::from_iter(row);
+        let caps = re.captures_iter(&tmp).count();
+        // dbg!(&tmp, caps);
+        part1_count += caps;
+    }
+
+    // also count overlapping
+    // previous regex would already have counted these once, so increment by 1
+    //   for the partial
+    let re2 = Regex::new(r"XMASAMX|SAMXMAS").unwrap();
+    for row in rows {
+        let tmp = String::from_iter(row);
+        let caps = re2.captures_iter(&tmp).count();
+        // dbg!(&tmp, caps);
+        part1_count += caps;
+    }
+
+    Ok(part1_count)
+}
+
+fn search_for_x_mas(ary: &Grid<[[char; 3]; 3]>) -> Result<usize> {
+    let mut count = 0;
+
+    // only X-patterns, not t-patterns!!!
+    // M . M
+    // . A .  => r"M.M.A.S.S"
+    // S . S
+
+    let re = Regex::new(r"M.M.A.S.S|S.M.A.S.M|S.S.A.M.M|M.S.A.M.S").unwrap();
+
+    for block in ary.as_slice() {
+        assert!(block[0][0] != '.');
+        assert!(block[2][2] != '.');
+
+        if block[1][1] == 'A' {
+            let flattened = String::from_iter(block.as_flattened());
+            let caps = re.captures_iter(&flattened).count();
+            count += caps;
+        }
+    }
+
+    Ok(count)
+}
+
+/// count forward and backwards on diagonals
+fn search_diagonals_for_xmas(ary: &Grid<char>) -> Result<usize> {
+    let mut count = 0;
+
+    for ((rr, cc), _) in ary.iter() {
+        // forward
+        if rr + 3 < ary.rows() && cc + 3 < ary.cols() {
+            let tmp: [char; 4] = std::array::from_fn(|ii| ary[(rr + ii, cc + ii)]);
+
+            // dbg!(rr, cc, tmp);
+            count += seach_for_xmas([&tmp[..]])?;
+        }
+
+        // backward
+        if rr + 3 < ary.rows() && cc >= 3 {
+            let tmp: [char; 4] = std::array::from_fn(|ii| ary[(rr + ii, cc - ii)]);
+
+            // dbg!(rr, cc, tmp);
+            count += seach_for_xmas([&tmp[..]])?;
+        }
+    }
+
+    Ok(count)
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    /// word search
+    type Input = Grid<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input_txt: &str) -> Result<Self::Input> {
+        Grid::parse_chars(input_txt)
+    }
+
+    /// count of XMAS in every direction
+    fn part1(ary: &Self::Input) -> Result<usize> {
+        let mut part1_count: usize = 0;
+
+        // count forward and backwards on the same line
+
+        part1_count += seach_for_xmas(ary.iter_rows())?;
+
+        // count forward and backwards on vertical
+
+        let xposed = ary.rotate_ccw();
+        part1_count += seach_for_xmas(xposed.iter_rows())?;
+
+        // count forward and backwards on diagonals
+        part1_count += search_diagonals_for_xmas(ary)?;
+
+        Ok(part1_count)
+    }
+
+    /// count of MAS crossed in the shape of an X
+    fn part2(ary: &Self::Input) -> Result<usize> {
+        let array_of_blocks = foreach3x3(ary)?;
+        search_for_x_mas(&array_of_blocks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+    use aoc_common::readme;
+
+    const README: &str = include_str!("../README.md");
+
+    aoc_common::readme_tests! {
+        Day4, README;
+        test_readme_1: part1 "For example:" => "XMAS occurs a total of";
+        test_readme_2: part2 "For example:" => "an X-MAS appears";
+    }
+
+    #[test]
+    fn test_1() {
+        const FINAL_ANSWER: usize = 18;
+
+        const ARY_ROWS_COLS: usize = 10;
+        let input_txt = readme::example(README, "For example:").unwrap();
+        let ary = Grid::parse_chars(&input_txt).unwrap();
+        assert_eq!(ary[(0, 0)], 'M');
+        assert_eq!(ary[(ARY_ROWS_COLS - 1, ARY_ROWS_COLS - 1)], 'X');
+
+        let mut count = 0;
+
+        count += seach_for_xmas(ary.iter_rows()).unwrap();
+        assert!(count < FINAL_ANSWER);
+
+        let rot90 = ary.rotate_ccw();
+        assert_eq!(rot90[(0, 0)], 'M');
+        assert_eq!(rot90[(ARY_ROWS_COLS - 1, ARY_ROWS_COLS - 1)], 'M');
+        count += seach_for_xmas(rot90.iter_rows()).unwrap();
+        assert!(count < FINAL_ANSWER);
+
+        // count forward and backwards on diagonals
+        count += search_diagonals_for_xmas(&ary).unwrap();
+
+        assert_eq!(count, FINAL_ANSWER);
+    }
+
+    #[test]
+    fn test_2() {
+        const FINAL_ANSWER: usize = 9;
+
+        let input_txt = readme::example(README, "For example:").unwrap();
+        let ary = Grid::parse_chars(&input_txt).unwrap();
+
+        let tmp = foreach3x3(&ary).unwrap();
+        let first_elem_of_row: [[char; 3]; 3] = tmp[(0, 0)];
+        assert_eq!(
+            String::from_iter(first_elem_of_row.as_flattened()),
+            "MMMMSAAMX"
+        );
+
+        let x_mas_count = search_for_x_mas(&tmp).unwrap();
+        assert_eq!(x_mas_count, FINAL_ANSWER);
+    }
+
+    #[test]
+    fn test_small() {
+        // too small for a 3x3 block, let alone an X
+        for input_txt in ["XMAS\n", "X\nM\nA\nS\n", "MAS\nMAS\n", "M\n"] {
+            let ary = Day4::parse(input_txt).unwrap();
+            assert_eq!(0, foreach3x3(&ary).unwrap().as_slice().len());
+            assert_eq!(0, Day4::part2(&ary).unwrap());
+        }
+        assert_eq!(
+            1,
+            Day4::part1(&Day4::parse("X\nM\nA\nS\n").unwrap()).unwrap()
+        );
+    }
+}
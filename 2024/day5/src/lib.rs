@@ -0,0 +1,584 @@
+#![doc = include_str!("../README.md")]
+
+use core::slice;
+use std::cmp::Ordering;
+
+use anyhow::{Result, anyhow};
+use aoc_common::{input::DefaultInput, parse, solution::Solution};
+
+/// where to find the puzzle input by default
+pub const INPUT: DefaultInput = aoc_common::default_input!();
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct Rule((u8, u8));
+
+impl Rule {
+    fn new(first: u8, second: u8) -> Self {
+        Rule((first, second))
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rules(Vec<Rule>);
+
+impl Rules {
+    fn new() -> Self {
+        Rules(vec![])
+    }
+}
+
+// copied from rustlib/src/rust/library/alloc/src/vec/mod.rs#L3458-L3466
+impl<'a> IntoIterator for &'a Rules {
+    type Item = &'a Rule;
+
+    type IntoIter = slice::Iter<'a, Rule>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum EvenOdd {
+    Even,
+    Odd,
+}
+
+fn is_evenodd(v: usize) -> EvenOdd {
+    if let 0 = v % 2 {
+        EvenOdd::Even
+    } else {
+        EvenOdd::Odd
+    }
+}
+
+// FIXME: fields are not fully orthogonal, should use enums
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Update {
+    pages: Vec<u8>,
+    correct: Option<bool>,
+    fixed_pages: Option<Vec<u8>>,
+}
+
+impl Update {
+    fn new(p: &[u8]) -> Self {
+        Update {
+            pages: p.to_vec(),
+            correct: None,
+            fixed_pages: None,
+        }
+    }
+
+    fn middle(&self) -> Result<u8> {
+        assert_eq!(is_evenodd(self.pages.len()), EvenOdd::Odd);
+        let mid_idx = self.pages.len() / 2;
+        Ok(*self.pages.get(mid_idx).unwrap())
+    }
+
+    // FIXME: DRY!!!
+    fn middle_fixed(&self) -> Result<u8> {
+        if self.fixed_pages.is_none() {
+            return Err(anyhow!("Missing fixed_pages in {:?}", self));
+        }
+
+        let pages = self.fixed_pages.as_ref().unwrap();
+
+        assert_eq!(is_evenodd(pages.len()), EvenOdd::Odd);
+        let mid_idx = pages.len() / 2;
+        Ok(*pages.get(mid_idx).unwrap())
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Updates(Vec<Update>);
+
+impl Updates {
+    fn new() -> Self {
+        Updates(vec![])
+    }
+}
+
+// copied from rustlib/src/rust/library/alloc/src/vec/mod.rs#L3469-L3476
+impl<'a> IntoIterator for &'a mut Updates {
+    type Item = &'a mut Update;
+    type IntoIter = slice::IterMut<'a, Update>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
+
+fn parse_input(input_txt: &str) -> Result<(Rules, Updates)> {
+    let mut result_r = Rules::new();
+    let mut result_u = Updates::new();
+
+    let [rules_lines, updates_lines] = &parse::sections(input_txt)[..] else {
+        return Err(anyhow!("expected a rules section and an updates section"));
+    };
+
+    for line in rules_lines {
+        match line.split_fields::<u8>("|", "a page number")?[..] {
+            [first, second] => result_r.0.push(Rule::new(first, second)),
+            _ => return Err(line.error(line.text, "a rule like 47|53").into()),
+        }
+    }
+
+    for line in updates_lines {
+        let pages: Vec<u8> = line.split_fields(",", "a page number")?;
+        if pages.len() < 2 {
+            return Err(line.error(line.text, "an update of 2 or more pages").into());
+        }
+        result_u.0.push(Update::new(&pages))
+    }
+
+    // not sure if sorting the rules is necessary
+    result_r.0.sort();
+
+    Ok((result_r, result_u))
+}
+
+/// Which pages have to come before which, a bit for every pair of page numbers
+///
+/// built once from the rules, so that checking or fixing an update never looks at the rules
+/// again
+#[derive(Clone, Debug)]
+pub struct Precedence {
+    /// bit `second` of row `first` is set for a rule `first|second`
+    before: Vec<[u64; 4]>,
+}
+
+impl Precedence {
+    pub fn new(rules: &Rules) -> Self {
+        let mut before = vec![[0; 4]; 256];
+        for rule in rules {
+            let (first, second) = rule.0;
+            before[first as usize][second as usize / 64] |= 1 << (second % 64);
+        }
+        Precedence { before }
+    }
+
+    /// whether a rule puts `first` before `second`
+    pub fn must_precede(&self, first: u8, second: u8) -> bool {
+        self.before[first as usize][second as usize / 64] & (1 << (second % 64)) != 0
+    }
+
+    /// `Less` if a rule puts `a` before `b`, `Greater` if one puts it after, `Equal` if no rule
+    /// (or a rule each way) relates them; for `sort_by` and `is_sorted_by`
+    pub fn compare(&self, a: u8, b: u8) -> Ordering {
+        match (self.must_precede(a, b), self.must_precede(b, a)) {
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            _ => Ordering::Equal,
+        }
+    }
+
+    /// whether every rule about two of the pages is obeyed, in one pass
+    ///
+    /// the same as `is_sorted_by` with [`Precedence::compare`] when the rules order every pair of
+    /// pages, but this also catches a broken rule between pages that aren't next to each other
+    pub fn is_ordered(&self, pages: &[u8]) -> bool {
+        let mut placed = [0u64; 4];
+        for &page in pages {
+            // a page that's already placed but has to come after this one
+            let after = &self.before[page as usize];
+            if (0..4).any(|w| after[w] & placed[w] != 0) {
+                return false;
+            }
+            placed[page as usize / 64] |= 1 << (page % 64);
+        }
+        true
+    }
+
+    /// whether the rules put every pair of the pages in order, one way round and without a
+    /// cycle, which is when [`Precedence::compare`] is a total order for them and safe to sort by
+    ///
+    /// in a total order the page with `k` pages after it has the next page's followers, and that
+    /// page, after it; with a row of bits per page that is one pass
+    fn orders_all(&self, pages: &[u8]) -> bool {
+        let mut update = [0u64; 4];
+        for &page in pages {
+            update[page as usize / 64] |= 1 << (page % 64);
+        }
+
+        // each page and the pages of the update it has to come before, by how many those are
+        let mut by_rank: Vec<Option<(u8, [u64; 4])>> = vec![None; pages.len()];
+        for &page in pages {
+            let row = &self.before[page as usize];
+            let after: [u64; 4] = std::array::from_fn(|w| row[w] & update[w]);
+            let rank = after.iter().map(|w| w.count_ones() as usize).sum::<usize>();
+            if rank >= pages.len() || by_rank[rank].replace((page, after)).is_some() {
+                return false;
+            }
+        }
+
+        let mut expected = [0u64; 4];
+        for (page, after) in by_rank.into_iter().flatten() {
+            if after != expected {
+                return false;
+            }
+            expected[page as usize / 64] |= 1 << (page % 64);
+        }
+        true
+    }
+
+    /// the pages in the order the rules give them, sorted in one pass when the rules order every
+    /// pair, otherwise see [`topological_order`] for what goes wrong
+    pub fn fix(&self, pages: &[u8]) -> Result<Vec<u8>> {
+        if !self.orders_all(pages) {
+            return topological_order(pages, self);
+        }
+        let mut fixed = pages.to_vec();
+        fixed.sort_by(|a, b| self.compare(*a, *b));
+        Ok(fixed)
+    }
+}
+
+fn check_updates(precedence: &Precedence, update_pages: &mut Updates) -> Result<()> {
+    for update in update_pages.into_iter() {
+        update.correct = Some(precedence.is_ordered(&update.pages));
+        // dbg!(&update);
+    }
+
+    Ok(())
+}
+
+/// the pages in the only order that obeys the rules, by Kahn's algorithm
+///
+/// errors if the rules have a cycle (naming its pages), otherwise if they leave two pages unordered
+fn topological_order(pages: &[u8], precedence: &Precedence) -> Result<Vec<u8>> {
+    // the rule graph by index into pages
+    let mut successors: Vec<Vec<usize>> = vec![vec![]; pages.len()];
+    let mut predecessors: Vec<Vec<usize>> = vec![vec![]; pages.len()];
+    let mut in_degree = vec![0; pages.len()];
+    for (first, &a) in pages.iter().enumerate() {
+        for (second, &b) in pages.iter().enumerate() {
+            if precedence.must_precede(a, b) {
+                successors[first].push(second);
+                predecessors[second].push(first);
+                in_degree[second] += 1;
+            }
+        }
+    }
+    let mut order = Vec::with_capacity(pages.len());
+    let mut ready: Vec<usize> = (0..pages.len()).filter(|&i| in_degree[i] == 0).collect();
+    // the first two pages that were ready together, only reported once there is no cycle
+    let mut unordered = None;
+    while !ready.is_empty() {
+        ready.sort();
+        if ready.len() > 1 && unordered.is_none() {
+            unordered = Some((pages[ready[0]], pages[ready[1]]));
+        }
+        let next = ready.remove(0);
+        order.push(pages[next]);
+        for &s in &successors[next] {
+            in_degree[s] -= 1;
+            if in_degree[s] == 0 {
+                ready.push(s);
+            }
+        }
+    }
+
+    if order.len() < pages.len() {
+        // every page left still has a page left before it, walk back through those until one
+        // comes round again
+        let mut walk = vec![(0..pages.len()).find(|&i| in_degree[i] > 0).unwrap()];
+        let cycle_start = loop {
+            let here = *walk.last().unwrap();
+            let before = *predecessors[here]
+                .iter()
+                .find(|&&p| in_degree[p] > 0)
+                .unwrap();
+            if let Some(pos) = walk.iter().position(|&w| w == before) {
+                break pos;
+            }
+            walk.push(before);
+        };
+        let mut cycle: Vec<usize> = walk[cycle_start..].iter().rev().copied().collect();
+        // start from the page that comes first in the update
+        let first = cycle.iter().enumerate().min_by_key(|(_, i)| **i).unwrap().0;
+        cycle.rotate_left(first);
+        cycle.push(cycle[0]);
+
+        let names: Vec<String> = cycle.iter().map(|&i| pages[i].to_string()).collect();
+        return Err(anyhow!(
+            "the rules for {pages:?} have a cycle: {}",
+            names.join(" → ")
+        ));
+    }
+
+    if let Some((a, b)) = unordered {
+        return Err(anyhow!(
+            "the order of {pages:?} is ambiguous: no rule puts {a} and {b} in order"
+        ));
+    }
+
+    Ok(order)
+}
+
+/// order the pages of each incorrect update by the rules
+fn fix_updates(precedence: &Precedence, update_pages: &mut Updates) -> Result<()> {
+    for update in update_pages
+        .into_iter()
+        .filter(|u: &&mut Update| u.correct == Some(false))
+    {
+        // dbg!(update);
+        let fixed = precedence.fix(&update.pages)?;
+        debug_assert!(precedence.is_ordered(&fixed));
+        update.fixed_pages = Some(fixed);
+    }
+
+    Ok(())
+}
+
+// ------------- PARTS --------------
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = (Rules, Updates);
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input_txt: &str) -> Result<Self::Input> {
+        parse_input(input_txt)
+    }
+
+    /// sum of the middle pages of the correctly-ordered updates
+    fn part1(input: &Self::Input) -> Result<u32> {
+        let (page_ordering_rules, mut update_pages) = input.clone();
+
+        // dbg!(page_ordering_rules, update_pages);
+
+        check_updates(&Precedence::new(&page_ordering_rules), &mut update_pages)?;
+
+        let mut sum_of_middle_page_nums: u32 = 0;
+        for update in update_pages.0.iter() {
+            if update.correct.is_some_and(|v| v) {
+                sum_of_middle_page_nums += update.middle()? as u32;
+            }
+        }
+
+        Ok(sum_of_middle_page_nums)
+    }
+
+    /// sum of the middle pages of the incorrectly-ordered updates, after fixing them
+    fn part2(input: &Self::Input) -> Result<u32> {
+        let (page_ordering_rules, mut update_pages) = input.clone();
+
+        let precedence = Precedence::new(&page_ordering_rules);
+        check_updates(&precedence, &mut update_pages)?;
+        fix_updates(&precedence, &mut update_pages)?;
+
+        let mut sum_of_middle_fixed_page_nums: u32 = 0;
+        for update in update_pages.0.iter() {
+            if update.correct.is_some_and(|v| !v) {
+                sum_of_middle_fixed_page_nums += update.middle_fixed()? as u32;
+            }
+        }
+
+        Ok(sum_of_middle_fixed_page_nums)
+    }
+}
+
+// ------------- TESTS --------------
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use aoc_common::readme;
+
+    const README: &str = include_str!("../README.md");
+
+    aoc_common::readme_tests! {
+        Day5, README;
+        test_readme_1: part1 "For example:" => "Adding these page numbers together gives";
+        test_readme_2: part2 "For example:" => "Adding these together produces";
+    }
+
+    #[test]
+    fn test1() {
+        const PARTIAL_ANSWER: [u8; 3] = [61, 53, 29];
+        const FINAL_ANSWER: u8 = 143;
+
+        let input_txt = readme::example(README, "For example:").unwrap();
+
+        let (page_ordering_rules, mut update_pages): (Rules, Updates) =
+            parse_input(&input_txt).unwrap();
+
+        check_updates(&Precedence::new(&page_ordering_rules), &mut update_pages).unwrap();
+
+        let mut sum_of_middle_page_nums = 0;
+        for (idx, update) in update_pages.0.iter().enumerate() {
+            if update.correct.is_some_and(|v| v) {
+                assert_eq!(PARTIAL_ANSWER[idx], update.middle().unwrap());
+                sum_of_middle_page_nums += update.middle().unwrap();
+            }
+        }
+        assert_eq!(FINAL_ANSWER, sum_of_middle_page_nums);
+    }
+
+    #[test]
+    fn test2() {
+        const PARTIAL_ANSWER: [u8; 6] = [0, 0, 0, 47, 29, 47];
+        const FINAL_ANSWER: u8 = 123;
+
+        let input_txt = readme::example(README, "For example:").unwrap();
+
+        let (page_ordering_rules, mut update_pages): (Rules, Updates) =
+            parse_input(&input_txt).unwrap();
+
+        let precedence = Precedence::new(&page_ordering_rules);
+        check_updates(&precedence, &mut update_pages).unwrap();
+
+        fix_updates(&precedence, &mut update_pages).unwrap();
+
+        let mut sum_of_middle_page_nums = 0;
+        for (idx, update) in update_pages.0.iter().enumerate() {
+            if update.correct.is_some_and(|v| !v) {
+                assert_eq!(PARTIAL_ANSWER[idx], update.middle_fixed().unwrap());
+                sum_of_middle_page_nums += update.middle_fixed().unwrap();
+            }
+        }
+        assert_eq!(FINAL_ANSWER, sum_of_middle_page_nums);
+    }
+
+    #[test]
+    fn test_topological_order() {
+        let rules = |pairs: &[(u8, u8)]| {
+            Precedence::new(&Rules(
+                pairs.iter().map(|&(a, b)| Rule::new(a, b)).collect(),
+            ))
+        };
+
+        assert_eq!(
+            vec![1, 2, 3],
+            topological_order(&[3, 1, 2], &rules(&[(1, 2), (1, 3), (2, 3)])).unwrap()
+        );
+
+        // nothing puts 2 and 3 in order
+        let err = topological_order(&[3, 2, 1], &rules(&[(1, 2), (1, 3)])).unwrap_err();
+        assert_eq!(
+            "the order of [3, 2, 1] is ambiguous: no rule puts 3 and 2 in order",
+            err.to_string()
+        );
+
+        // a cycle of all the pages
+        let err = topological_order(&[1, 2, 3], &rules(&[(1, 2), (2, 3), (3, 1)])).unwrap_err();
+        assert_eq!(
+            "the rules for [1, 2, 3] have a cycle: 1 → 2 → 3 → 1",
+            err.to_string()
+        );
+
+        // a cycle after a page that can be placed
+        let err = topological_order(&[4, 3, 2, 1], &rules(&[(1, 2), (2, 3), (3, 2), (4, 1)]))
+            .unwrap_err();
+        assert_eq!(
+            "the rules for [4, 3, 2, 1] have a cycle: 3 → 2 → 3",
+            err.to_string()
+        );
+
+        // a cycle next to pages no rule orders is still a cycle, not an ambiguity
+        let err = topological_order(&[5, 6, 1, 2, 7], &rules(&[(1, 2), (2, 1)])).unwrap_err();
+        assert_eq!(
+            "the rules for [5, 6, 1, 2, 7] have a cycle: 1 → 2 → 1",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_precedence() {
+        let input_txt = readme::example(README, "For example:").unwrap();
+        let (page_ordering_rules, update_pages) = parse_input(&input_txt).unwrap();
+        let precedence = Precedence::new(&page_ordering_rules);
+
+        assert!(precedence.must_precede(47, 53) && !precedence.must_precede(53, 47));
+        assert_eq!(Ordering::Less, precedence.compare(47, 53));
+        assert_eq!(Ordering::Greater, precedence.compare(53, 47));
+        assert_eq!(Ordering::Equal, precedence.compare(47, 47));
+
+        // the example's rules order every pair in each update, so sorting is enough
+        let fixed: [&[u8]; 6] = [
+            &[75, 47, 61, 53, 29],
+            &[97, 61, 53, 29, 13],
+            &[75, 29, 13],
+            &[97, 75, 47, 61, 53],
+            &[61, 29, 13],
+            &[97, 75, 47, 29, 13],
+        ];
+        for (update, fixed) in update_pages.0.iter().zip(fixed) {
+            let ordered = precedence.is_ordered(&update.pages);
+            assert_eq!(
+                ordered,
+                update
+                    .pages
+                    .is_sorted_by(|a, b| precedence.compare(*a, *b).is_le())
+            );
+            assert_eq!(ordered, update.pages == fixed);
+            assert_eq!(fixed, precedence.fix(&update.pages).unwrap());
+        }
+
+        // a broken rule between pages that aren't next to each other
+        let rules = Rules(vec![Rule::new(1, 3)]);
+        let precedence = Precedence::new(&rules);
+        assert!(!precedence.is_ordered(&[3, 2, 1]));
+        assert!([3, 2, 1].is_sorted_by(|a, b| precedence.compare(*a, *b).is_le()));
+        assert!(precedence.fix(&[3, 2, 1]).is_err());
+
+        // a rule between each pair of neighbours, and one back round from the last to the first
+        let rules = Rules(vec![Rule::new(1, 2), Rule::new(2, 3), Rule::new(3, 1)]);
+        let precedence = Precedence::new(&rules);
+        assert_eq!(
+            "the rules for [1, 2, 3] have a cycle: 1 → 2 → 3 → 1",
+            precedence.fix(&[1, 2, 3]).unwrap_err().to_string()
+        );
+
+        // more pages than the standard library sorts by insertion, where sorting by a comparator
+        // that isn't a total order may panic; a rule back round makes a cycle, a missing one leaves
+        // two pages unordered
+        let pages: Vec<u8> = (0..41).rev().collect();
+        let all_pairs = |skip: (u8, u8)| {
+            (0..41u8)
+                .flat_map(|a| (a + 1..41).map(move |b| (a, b)))
+                .filter(move |&pair| pair != skip)
+                .map(|(a, b)| Rule::new(a, b))
+        };
+        let mut rules = Rules(all_pairs((0, 0)).collect());
+        rules.0.push(Rule::new(40, 0));
+        let err = Precedence::new(&rules).fix(&pages).unwrap_err().to_string();
+        assert!(err.contains("have a cycle: 40 → 0 → "), "{err}");
+        let rules = Rules(all_pairs((10, 11)).collect());
+        let err = Precedence::new(&rules).fix(&pages).unwrap_err().to_string();
+        assert!(err.ends_with("no rule puts 11 and 10 in order"), "{err}");
+
+        // and any rules at all, from a small LCG
+        let mut state: u32 = 1;
+        for _ in 0..20 {
+            let rules = Rules(
+                (0..400)
+                    .map(|_| {
+                        state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                        Rule::new((state >> 8) as u8 % 41, (state >> 20) as u8 % 41)
+                    })
+                    .collect(),
+            );
+            let precedence = Precedence::new(&rules);
+            if let Ok(fixed) = precedence.fix(&pages) {
+                assert!(precedence.is_ordered(&fixed));
+            }
+        }
+
+        // more pages than the standard library sorts by insertion
+        let rules = Rules(
+            (0..40u8)
+                .flat_map(|a| (a + 1..40).map(move |b| Rule::new(a, b)))
+                .collect(),
+        );
+        let pages: Vec<u8> = (0..40).rev().collect();
+        let precedence = Precedence::new(&rules);
+        assert!(!precedence.is_ordered(&pages));
+        assert_eq!(
+            (0..40).collect::<Vec<u8>>(),
+            precedence.fix(&pages).unwrap()
+        );
+    }
+}
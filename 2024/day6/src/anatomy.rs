@@ -0,0 +1,333 @@
+//! What the loops from part 2 look like: how long the guard walks before it is stuck, how long
+//! its cycle is, and where the cycle goes
+//!
+//! not every loop goes round the new obstruction, some only send the guard into a cycle made of
+//! obstructions that were already there
+
+use crate::{
+    Guard, Map, Orientation, Outcome,
+    jump::JumpTable,
+    rules::{Rules, states_with},
+};
+use aoc_common::parallel;
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display},
+};
+
+/// The loop that a new obstruction traps the guard in
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoopAnatomy {
+    pub obstruction: (usize, usize),
+    /// steps from the guard's start before its first time round the cycle
+    pub lead_in: usize,
+    /// steps (moves and turns) once round the cycle
+    pub length: usize,
+    /// positions on the cycle, in row-major order
+    pub cells: Vec<(usize, usize)>,
+    /// the obstruction's neighbours (above, right, below, left) that the cycle passes through
+    pub neighbours_on_cycle: Vec<(usize, usize)>,
+}
+
+impl LoopAnatomy {
+    /// the loop that an obstruction at `coord` makes under `rules`, `None` if the guard still
+    /// gets out
+    pub fn new(map_unvisited: &Map, rules: &Rules, coord: (usize, usize)) -> Option<Self> {
+        if *rules == Rules::default() {
+            Self::jumped(map_unvisited, &mut JumpTable::new(map_unvisited), coord)
+        } else {
+            Self::walked(map_unvisited, rules, coord)
+        }
+    }
+
+    /// under the puzzle's rules, from one jump patrol on `table`, the map's own table, which is
+    /// left as it was
+    fn jumped(map_unvisited: &Map, table: &mut JumpTable, coord: (usize, usize)) -> Option<Self> {
+        table.set_obstruction(coord, true);
+        let jumps = table.patrol(&map_unvisited.guard, None);
+        table.set_obstruction(coord, false);
+
+        let Outcome::Loop { start, length } = jumps.outcome else {
+            return None;
+        };
+        let cycle = (start..start + length).map(|step| jumps.state_at(step));
+        Some(Self::measure(map_unvisited, coord, start, length, cycle))
+    }
+
+    /// under any `rules`, from one patrol a step at a time
+    fn walked(map_unvisited: &Map, rules: &Rules, coord: (usize, usize)) -> Option<Self> {
+        let mut blocked = map_unvisited.clone();
+        blocked.set_obstruction(coord, true);
+
+        let (outcome, states) = states_with(&blocked, rules, None);
+        let Outcome::Loop { start, length } = outcome else {
+            return None;
+        };
+        let cycle = states.into_iter().skip(start).take(length);
+        Some(Self::measure(map_unvisited, coord, start, length, cycle))
+    }
+
+    /// the guard's states once round the cycle, from step `start`
+    fn measure(
+        map_unvisited: &Map,
+        coord: (usize, usize),
+        start: usize,
+        length: usize,
+        cycle: impl Iterator<Item = Guard>,
+    ) -> Self {
+        let cells: BTreeSet<(usize, usize)> = cycle.map(|g| (g.row, g.col)).collect();
+        let neighbours_on_cycle = Orientation::ALL
+            .iter()
+            .filter_map(|o| o.step(coord.0, coord.1, map_unvisited.rows(), map_unvisited.cols()))
+            .filter(|uu| cells.contains(uu))
+            .collect();
+
+        Self {
+            obstruction: coord,
+            lead_in: start,
+            length,
+            cells: cells.into_iter().collect(),
+            neighbours_on_cycle,
+        }
+    }
+
+    /// whether the guard comes past the new obstruction on its cycle, rather than only being
+    /// sent into a cycle elsewhere
+    pub fn touches_obstruction(&self) -> bool {
+        !self.neighbours_on_cycle.is_empty()
+    }
+}
+
+/// each of `loops` under `rules`, as found by [`loop_obstructions`](crate::loop_obstructions)
+/// or [`loop_obstructions_with`](crate::rules::loop_obstructions_with), in the same order,
+/// spread over `threads` threads
+///
+/// each loop is walked once, and jumped from turn to turn under the puzzle's rules
+pub fn loop_anatomy(
+    map_unvisited: &Map,
+    rules: &Rules,
+    loops: &[(usize, usize)],
+    threads: usize,
+) -> Vec<LoopAnatomy> {
+    if *rules == Rules::default() {
+        parallel::filter_map_with(
+            loops,
+            threads,
+            || JumpTable::new(map_unvisited),
+            |table, &coord| LoopAnatomy::jumped(map_unvisited, table, coord),
+        )
+    } else {
+        parallel::filter_map(loops, threads, |&coord| {
+            LoopAnatomy::walked(map_unvisited, rules, coord)
+        })
+    }
+}
+
+/// Counts in power-of-two buckets: `0..=1`, `2..=3`, `4..=7`, ...
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Histogram {
+    buckets: Vec<usize>,
+}
+
+impl Histogram {
+    pub fn add(&mut self, value: usize) {
+        let bucket = (usize::BITS - value.leading_zeros()).saturating_sub(1) as usize;
+        if self.buckets.len() <= bucket {
+            self.buckets.resize(bucket + 1, 0);
+        }
+        self.buckets[bucket] += 1;
+    }
+
+    /// (lowest value, highest value, count) of each bucket, up to the highest one used
+    pub fn buckets(&self) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        self.buckets.iter().enumerate().map(|(bucket, &count)| {
+            let lo = if bucket == 0 { 0 } else { 1 << bucket };
+            (lo, (2 << bucket) - 1, count)
+        })
+    }
+}
+
+impl Display for Histogram {
+    /// a bar per bucket, the longest one 40 wide
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let most = self.buckets.iter().copied().max().unwrap_or(0).max(1);
+        for (lo, hi, count) in self.buckets() {
+            let bar = "#".repeat((count * 40).div_ceil(most));
+            writeln!(f, "{:>12} | {bar:<40} {count}", format!("{lo}-{hi}"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Loop anatomy of a whole map at a glance
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub loops: usize,
+    /// loops whose cycle comes past the new obstruction
+    pub touching: usize,
+    pub lengths: Histogram,
+    pub lead_ins: Histogram,
+}
+
+impl Summary {
+    pub fn new(anatomies: &[LoopAnatomy]) -> Self {
+        let mut summary = Self::default();
+        for a in anatomies {
+            summary.loops += 1;
+            summary.touching += usize::from(a.touches_obstruction());
+            summary.lengths.add(a.length);
+            summary.lead_ins.add(a.lead_in);
+        }
+        summary
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} loops, {} of them come past the new obstruction",
+            self.loops, self.touching
+        )?;
+        writeln!(f, "cycle lengths:\n{}", self.lengths)?;
+        write!(f, "steps before the cycle:\n{}", self.lead_ins)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::anatomy::*;
+    use crate::rules::{Edge, loop_obstructions_with};
+    use crate::{Search, loop_obstructions};
+    use aoc_common::readme;
+    use std::str::FromStr;
+
+    fn example() -> Map {
+        let input_txt = readme::example(include_str!("../README.md"), "For example:").unwrap();
+        Map::from_str(&input_txt).unwrap()
+    }
+
+    #[test]
+    fn test_loop_anatomy() {
+        let map_unvisited = example();
+        let loops = loop_obstructions(&map_unvisited, 2, Search::FromPrefix);
+        let anatomies = loop_anatomy(&map_unvisited, &Rules::default(), &loops, 2);
+
+        let shapes: Vec<_> = anatomies
+            .iter()
+            .map(|a| (a.obstruction, a.lead_in, a.length))
+            .collect();
+        assert_eq!(
+            vec![
+                ((6, 3), 0, 22),
+                ((7, 6), 19, 16),
+                ((7, 7), 35, 16),
+                ((8, 1), 24, 20),
+                ((8, 3), 0, 42),
+                ((9, 7), 37, 18)
+            ],
+            shapes
+        );
+
+        // option two goes round a rectangle, turning in front of the new obstruction
+        let two = &anatomies[1];
+        assert_eq!(12, two.cells.len());
+        assert!(two.cells.contains(&(4, 2)) && two.cells.contains(&(6, 6)));
+        assert!(!two.cells.contains(&(5, 4)));
+        assert_eq!(vec![(6, 6)], two.neighbours_on_cycle);
+        assert!(two.touches_obstruction());
+
+        // the same as a step at a time
+        for (coord, a) in loops.iter().zip(&anatomies) {
+            assert_eq!(
+                Some(a),
+                LoopAnatomy::walked(&map_unvisited, &Rules::default(), *coord).as_ref()
+            );
+        }
+
+        // not a loop
+        assert_eq!(
+            None,
+            LoopAnatomy::new(&map_unvisited, &Rules::default(), (0, 0))
+        );
+        assert_eq!(
+            None,
+            LoopAnatomy::walked(&map_unvisited, &Rules::default(), (0, 0))
+        );
+    }
+
+    #[test]
+    fn test_other_rules() {
+        let map_unvisited = example();
+        for edge in [Edge::Wrap, Edge::Wall] {
+            let rules = Rules {
+                edge,
+                ..Rules::default()
+            };
+            // the guard never leaves, so every position on its path makes a loop
+            let loops = loop_obstructions_with(&map_unvisited, &rules, 1);
+            let anatomies = loop_anatomy(&map_unvisited, &rules, &loops, 2);
+            assert_eq!(
+                loops,
+                anatomies.iter().map(|a| a.obstruction).collect::<Vec<_>>()
+            );
+            for a in &anatomies {
+                let mut blocked = map_unvisited.clone();
+                blocked.set_obstruction(a.obstruction, true);
+                assert_eq!(
+                    Outcome::Loop {
+                        start: a.lead_in,
+                        length: a.length
+                    },
+                    blocked.patrol_with(&rules, None),
+                    "{edge:?} {:?}",
+                    a.obstruction
+                );
+                assert!(!a.cells.is_empty() && !a.cells.contains(&a.obstruction));
+            }
+        }
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut histogram = Histogram::default();
+        for value in [0, 1, 3, 4, 7, 8] {
+            histogram.add(value);
+        }
+        assert_eq!(
+            vec![(0, 1, 2), (2, 3, 1), (4, 7, 2), (8, 15, 1)],
+            histogram.buckets().collect::<Vec<_>>()
+        );
+        assert!(
+            histogram
+                .to_string()
+                .starts_with("         0-1 | ######################################## 2\n")
+        );
+
+        let map_unvisited = example();
+        let loops = loop_obstructions(&map_unvisited, 1, Search::FromPrefix);
+        let summary = Summary::new(&loop_anatomy(&map_unvisited, &Rules::default(), &loops, 1));
+        assert_eq!(6, summary.loops);
+        assert_eq!(
+            6,
+            summary
+                .lengths
+                .buckets()
+                .map(|(_, _, count)| count)
+                .sum::<usize>()
+        );
+        // 16, 16, 18, 20 and 22 steps, then 42
+        assert_eq!(
+            vec![
+                (0, 1, 0),
+                (2, 3, 0),
+                (4, 7, 0),
+                (8, 15, 0),
+                (16, 31, 5),
+                (32, 63, 1)
+            ],
+            summary.lengths.buckets().collect::<Vec<_>>()
+        );
+    }
+}
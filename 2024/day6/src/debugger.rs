@@ -0,0 +1,358 @@
+//! Step the guard forwards and backwards by hand, in the terminal or from tests
+//!
+//! every change to the map goes through [`Debugger`], which keeps enough history to undo it
+
+use crate::{Guard, Map};
+use anyhow::Result;
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute, terminal,
+};
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+};
+
+/// one undoable change to the map
+#[derive(Clone, Debug, PartialEq)]
+enum Change {
+    /// the guard stepped forward from `from` and visited the position it stepped into, which
+    /// had the mask `marked` before
+    Moved { from: Guard, marked: u8 },
+    /// the guard turned right on the spot, where the mask was `marked` before
+    Turned { from: Guard, marked: u8 },
+    /// an obstruction was added or removed
+    Toggled { coord: (usize, usize) },
+}
+
+/// A map that the guard patrols one step at a time, with undo
+#[derive(Clone, Debug)]
+pub struct Debugger {
+    /// the map as it was given, to tell added obstructions apart
+    original: Map,
+    map: Map,
+    history: Vec<Change>,
+    /// moves and turns so far
+    steps: usize,
+}
+
+impl Debugger {
+    pub fn new(map_unvisited: &Map) -> Self {
+        Self {
+            original: map_unvisited.clone(),
+            map: map_unvisited.clone(),
+            history: Vec::new(),
+            steps: 0,
+        }
+    }
+
+    pub fn map(&self) -> &Map {
+        &self.map
+    }
+
+    pub fn guard(&self) -> &Guard {
+        &self.map.guard
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// true once the guard is facing off the edge of the map
+    pub fn exited(&self) -> bool {
+        self.map.coord_in_front_of_guard().is_none()
+    }
+
+    /// one step forward (a move or a turn), or `None` if the guard has left the map
+    pub fn step(&mut self) -> Option<&Guard> {
+        let from = self.map.guard.clone();
+        let marked = self.map.step()?;
+        self.history
+            .push(if self.map.guard.orientation == from.orientation {
+                Change::Moved { from, marked }
+            } else {
+                Change::Turned { from, marked }
+            });
+        self.steps += 1;
+        Some(&self.map.guard)
+    }
+
+    /// step until the guard has turned (or left the map), returns the number of steps taken
+    pub fn next_turn(&mut self) -> usize {
+        let mut taken = 0;
+        while self.step().is_some() {
+            taken += 1;
+            if matches!(self.history.last(), Some(Change::Turned { .. })) {
+                break;
+            }
+        }
+        taken
+    }
+
+    /// add or remove an obstruction, not where the guard is standing; returns whether it changed
+    pub fn toggle(&mut self, coord: (usize, usize)) -> bool {
+        if coord == (self.map.guard.row, self.map.guard.col) {
+            return false;
+        }
+        self.map
+            .set_obstruction(coord, !self.map.is_obstruction(coord));
+        self.history.push(Change::Toggled { coord });
+        true
+    }
+
+    /// take back the last step or toggle, returns false when there is nothing to undo
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(Change::Moved { from, marked } | Change::Turned { from, marked }) => {
+                let here = (self.map.guard.row, self.map.guard.col);
+                self.map.set_mask(here, marked);
+                self.map.guard = from;
+                self.steps -= 1;
+            }
+            Some(Change::Toggled { coord }) => {
+                self.map
+                    .set_obstruction(coord, !self.map.is_obstruction(coord));
+            }
+            None => return false,
+        }
+        true
+    }
+
+    /// the part of the map at `top_left` with `size` (rows, cols), highlighting `cursor`
+    ///
+    /// `X` is a visited position and `O` an added obstruction, as in the puzzle
+    pub fn render(
+        &self,
+        cursor: Option<(usize, usize)>,
+        top_left: (usize, usize),
+        size: (usize, usize),
+    ) -> String {
+        let rows = top_left.0..(top_left.0 + size.0).min(self.map.rows());
+        let cols = top_left.1..(top_left.1 + size.1).min(self.map.cols());
+
+        let mut out = String::new();
+        for rr in rows {
+            for cc in cols.clone() {
+                let obstruction = self.map.is_obstruction((rr, cc));
+                let glyph = if (rr, cc) == (self.map.guard.row, self.map.guard.col) {
+                    self.map.guard.glyph()
+                } else if obstruction && !self.original.is_obstruction((rr, cc)) {
+                    'O'
+                } else if obstruction {
+                    '#'
+                } else if self.map.is_visited((rr, cc)) {
+                    'X'
+                } else {
+                    '.'
+                };
+                if cursor == Some((rr, cc)) {
+                    write!(out, "\x1b[7m{glyph}\x1b[0m").unwrap();
+                } else {
+                    out.push(glyph);
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// one line about the guard, for under the map
+    pub fn status(&self) -> String {
+        let g = &self.map.guard;
+        format!(
+            "step {}: guard at ({}, {}) facing {:?}{}",
+            self.steps,
+            g.row,
+            g.col,
+            g.orientation,
+            if self.exited() {
+                ", leaving the map"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+/// first row/col of a `size` window onto `len` rows/cols, keeping `center` in the middle
+fn scroll(center: usize, size: usize, len: usize) -> usize {
+    center
+        .saturating_sub(size / 2)
+        .min(len.saturating_sub(size))
+}
+
+const HELP: &str = "n/space: step  b/backspace: undo  t: next turn  arrows: cursor  o: obstruction  g: cursor to guard  q: quit";
+
+/// puts the terminal back when dropped, however [`interactive`] ends
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enable() -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        // nothing more to do if these fail, the terminal is as restored as it gets
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// step through the patrol in the terminal until `q` or Esc
+pub fn interactive(map_unvisited: &Map) -> Result<()> {
+    let mut stdout = io::stdout();
+    let _raw = RawTerminal::enable()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    interact(&mut Debugger::new(map_unvisited), &mut stdout)
+}
+
+fn interact(debugger: &mut Debugger, stdout: &mut io::Stdout) -> Result<()> {
+    let (rows, cols) = (debugger.map().rows(), debugger.map().cols());
+    let mut cursor = (debugger.guard().row, debugger.guard().col);
+
+    loop {
+        // leave room for the status and help lines
+        let (width, height) = terminal::size()?;
+        let size = ((height as usize).saturating_sub(3).max(1), width as usize);
+        let top_left = (
+            scroll(cursor.0, size.0, rows),
+            scroll(cursor.1, size.1, cols),
+        );
+
+        let screen = debugger.render(Some(cursor), top_left, size);
+        execute!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(terminal::ClearType::All)
+        )?;
+        // raw mode doesn't return the cursor to the start of the line
+        write!(stdout, "{}", screen.replace('\n', "\r\n"))?;
+        write!(
+            stdout,
+            "{}, cursor at ({}, {})\r\n{HELP}",
+            debugger.status(),
+            cursor.0,
+            cursor.1
+        )?;
+        stdout.flush()?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Char('n') | KeyCode::Char(' ') => {
+                debugger.step();
+            }
+            KeyCode::Char('b') | KeyCode::Backspace => {
+                debugger.undo();
+            }
+            KeyCode::Char('t') => {
+                debugger.next_turn();
+            }
+            KeyCode::Char('o') => {
+                debugger.toggle(cursor);
+            }
+            KeyCode::Char('g') => cursor = (debugger.guard().row, debugger.guard().col),
+            KeyCode::Up => cursor.0 = cursor.0.saturating_sub(1),
+            KeyCode::Down => cursor.0 = (cursor.0 + 1).min(rows - 1),
+            KeyCode::Left => cursor.1 = cursor.1.saturating_sub(1),
+            KeyCode::Right => cursor.1 = (cursor.1 + 1).min(cols - 1),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::Orientation;
+    use crate::debugger::*;
+    use aoc_common::readme;
+    use std::str::FromStr;
+
+    fn example() -> Map {
+        let input_txt = readme::example(include_str!("../README.md"), "For example:").unwrap();
+        Map::from_str(&input_txt).unwrap()
+    }
+
+    #[test]
+    fn test_step_undo() {
+        let map_unvisited = example();
+        let mut debugger = Debugger::new(&map_unvisited);
+
+        // up to the first turn, and back again
+        assert_eq!(6, debugger.next_turn());
+        assert_eq!(&Guard::new(1, 4, Orientation::Right), debugger.guard());
+        assert_eq!(6, debugger.map().count_positions_visited());
+        assert!(debugger.undo());
+        assert_eq!(&Guard::new(1, 4, Orientation::Up), debugger.guard());
+        for _ in 0..5 {
+            assert!(debugger.undo());
+        }
+        assert!(!debugger.undo());
+        assert_eq!(0, debugger.steps());
+        assert_eq!(map_unvisited.to_string(), debugger.map().to_string());
+        assert_eq!(1, debugger.map().count_positions_visited());
+
+        // all the way out, then undo everything
+        while debugger.step().is_some() {}
+        assert!(debugger.exited());
+        assert_eq!(54, debugger.steps());
+        assert_eq!(41, debugger.map().count_positions_visited());
+        while debugger.undo() {}
+        assert_eq!(1, debugger.map().count_positions_visited());
+    }
+
+    #[test]
+    fn test_toggle() {
+        let map_unvisited = example();
+        let mut debugger = Debugger::new(&map_unvisited);
+
+        // not on the guard
+        assert!(!debugger.toggle((6, 4)));
+
+        // remove the obstruction that makes the first turn, the guard walks off the top
+        assert!(debugger.toggle((0, 4)));
+        assert_eq!(6, debugger.next_turn());
+        assert!(debugger.exited());
+        assert_eq!(&Guard::new(0, 4, Orientation::Up), debugger.guard());
+
+        // undo back past the toggle, and the guard turns again
+        for _ in 0..7 {
+            assert!(debugger.undo());
+        }
+        assert_eq!(6, debugger.next_turn());
+        assert_eq!(Orientation::Right, debugger.guard().orientation);
+
+        // an added obstruction is drawn as O, the visited positions as X
+        assert!(debugger.toggle((2, 5)));
+        let screen = debugger.render(None, (0, 0), (3, 10));
+        assert_eq!("....#.....\n....>....#\n....XO....\n", screen);
+        assert!(
+            debugger
+                .status()
+                .starts_with("step 6: guard at (1, 4) facing Right")
+        );
+
+        // the window and the cursor
+        let screen = debugger.render(Some((1, 4)), (1, 3), (2, 3));
+        assert_eq!(".\x1b[7m>\x1b[0m.\n.XO\n", screen);
+    }
+
+    #[test]
+    fn test_scroll() {
+        assert_eq!(0, scroll(3, 10, 130));
+        assert_eq!(60, scroll(65, 10, 130));
+        assert_eq!(120, scroll(129, 10, 130));
+        assert_eq!(0, scroll(5, 200, 130));
+    }
+}
@@ -0,0 +1,350 @@
+//! Image frames of the guard's patrol, as numbered PPM or PNG files or a single animated GIF
+
+use crate::{Guard, Map, Outcome};
+use anyhow::{Context, Result, anyhow};
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// image file format of the frames
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// a directory of binary PPM (P6) files
+    Ppm,
+    /// a directory of PNG files
+    Png,
+    /// one animated GIF
+    Gif,
+}
+
+/// colours of a frame, index into [`PALETTE`]
+const FLOOR: u8 = 0;
+const OBSTRUCTION: u8 = 1;
+const NEW_OBSTRUCTION: u8 = 2;
+const VISITED: u8 = 3;
+const LOOP: u8 = 4;
+const GUARD: u8 = 5;
+
+/// RGB for each colour of a frame
+const PALETTE: [[u8; 3]; 6] = [
+    [24, 24, 32],
+    [150, 150, 160],
+    [230, 60, 60],
+    [60, 110, 200],
+    [240, 140, 40],
+    [250, 220, 60],
+];
+
+/// One picture of the map, `cell_size` pixels square per position
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    /// row-major index into the palette for each pixel
+    pub pixels: Vec<u8>,
+}
+
+impl Image {
+    /// pixels as RGB triples
+    fn rgb(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|&p| PALETTE[p as usize])
+            .collect()
+    }
+
+    pub fn write_ppm(&self, mut w: impl Write) -> Result<()> {
+        write!(w, "P6\n{} {}\n255\n", self.width, self.height)?;
+        w.write_all(&self.rgb())?;
+        Ok(())
+    }
+
+    pub fn write_png(&self, w: impl Write) -> Result<()> {
+        let mut encoder = png::Encoder::new(w, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_palette(PALETTE.as_flattened());
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        Ok(())
+    }
+}
+
+/// How to turn a patrol into frames
+#[derive(Clone, Debug)]
+pub struct Export {
+    pub format: Format,
+    /// pixels along each side of a position
+    pub cell_size: usize,
+    /// steps between frames, the last step always gets a frame
+    pub stride: usize,
+    /// new obstruction to place before patrolling, its loop (if any) is drawn in its own colour
+    pub obstruction: Option<(usize, usize)>,
+    /// GIF delay between frames, in hundredths of a second
+    pub delay: u16,
+}
+
+impl Default for Export {
+    fn default() -> Self {
+        Self {
+            format: Format::Gif,
+            cell_size: 4,
+            stride: 1,
+            obstruction: None,
+            delay: 5,
+        }
+    }
+}
+
+impl Export {
+    /// hand each frame of the patrol to `frame`, one for the start and one every `stride` steps
+    ///
+    /// a looping guard is followed once around its loop; returns the number of frames
+    pub fn frames(
+        &self,
+        map_unvisited: &Map,
+        mut frame: impl FnMut(&Image) -> Result<()>,
+    ) -> Result<usize> {
+        if self.cell_size == 0 || self.stride == 0 {
+            return Err(anyhow!("cell size and stride must be at least 1"));
+        }
+
+        let mut map = map_unvisited.clone();
+        if let Some(coord) = self.obstruction {
+            if coord.0 >= map.rows() || coord.1 >= map.cols() {
+                return Err(anyhow!("obstruction {coord:?} is off the map"));
+            }
+            if coord == (map.guard.row, map.guard.col) {
+                return Err(anyhow!("obstruction {coord:?} is on the guard"));
+            }
+            map.set_obstruction(coord, true);
+        }
+
+        // how far to walk, and from which step the guard is going round its loop
+        let (end, loop_start) = match map.clone().patrol(None) {
+            Outcome::Exited { steps } => (steps, None),
+            Outcome::Loop { start, length } => (start + length, Some(start)),
+            Outcome::BudgetExhausted { .. } => unreachable!("patrol without a budget"),
+        };
+
+        let mut image = Image {
+            width: map.cols() * self.cell_size,
+            height: map.rows() * self.cell_size,
+            pixels: vec![FLOOR; map.cols() * self.cell_size * map.rows() * self.cell_size],
+        };
+        for coord in map.coords() {
+            if Some(coord) == self.obstruction {
+                self.paint(&mut image, coord, NEW_OBSTRUCTION);
+            } else if map.is_obstruction(coord) {
+                self.paint(&mut image, coord, OBSTRUCTION);
+            }
+        }
+        self.paint(&mut image, (map.guard.row, map.guard.col), GUARD);
+        frame(&image)?;
+        let mut count = 1;
+
+        let mut before: Guard = map.guard.clone();
+        for (idx, step) in (&mut map).take(end).enumerate() {
+            let colour = match loop_start {
+                Some(start) if idx >= start => LOOP,
+                _ => VISITED,
+            };
+            self.paint(&mut image, (before.row, before.col), colour);
+            self.paint(&mut image, (step.row, step.col), GUARD);
+            before = step;
+
+            let step_number = idx + 1;
+            if step_number % self.stride == 0 || step_number == end {
+                frame(&image)?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// fill the square for `(row, col)` with `colour`
+    fn paint(&self, image: &mut Image, (row, col): (usize, usize), colour: u8) {
+        for yy in row * self.cell_size..(row + 1) * self.cell_size {
+            let start = yy * image.width + col * self.cell_size;
+            image.pixels[start..start + self.cell_size].fill(colour);
+        }
+    }
+
+    /// write the frames of the patrol to `path`, a directory for PPM and PNG or a file for GIF
+    ///
+    /// returns the number of frames written
+    pub fn write(&self, map_unvisited: &Map, path: &Path) -> Result<usize> {
+        match self.format {
+            Format::Ppm | Format::Png => {
+                fs::create_dir_all(path)
+                    .with_context(|| format!("could not create {}", path.display()))?;
+                let ext = if self.format == Format::Ppm {
+                    "ppm"
+                } else {
+                    "png"
+                };
+                let mut idx = 0;
+                self.frames(map_unvisited, |image| {
+                    let frame_path: PathBuf = path.join(format!("frame{idx:05}.{ext}"));
+                    let w =
+                        BufWriter::new(File::create(&frame_path).with_context(|| {
+                            format!("could not create {}", frame_path.display())
+                        })?);
+                    idx += 1;
+                    match self.format {
+                        Format::Ppm => image.write_ppm(w),
+                        _ => image.write_png(w),
+                    }
+                })
+            }
+            Format::Gif => {
+                let w = BufWriter::new(
+                    File::create(path)
+                        .with_context(|| format!("could not create {}", path.display()))?,
+                );
+                self.write_gif(map_unvisited, w)
+            }
+        }
+    }
+
+    /// write the frames of the patrol as one animated GIF
+    pub fn write_gif(&self, map_unvisited: &Map, w: impl Write) -> Result<usize> {
+        let (width, height) = (
+            map_unvisited.cols() * self.cell_size,
+            map_unvisited.rows() * self.cell_size,
+        );
+        let (width, height) = (
+            u16::try_from(width).context("frame is too wide for a GIF")?,
+            u16::try_from(height).context("frame is too tall for a GIF")?,
+        );
+        let mut encoder = gif::Encoder::new(w, width, height, PALETTE.as_flattened())?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+
+        self.frames(map_unvisited, |image| {
+            let frame = gif::Frame {
+                width,
+                height,
+                delay: self.delay,
+                buffer: image.pixels.as_slice().into(),
+                ..gif::Frame::default()
+            };
+            encoder.write_frame(&frame)?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::export::*;
+    use aoc_common::readme;
+    use std::{io::Cursor, str::FromStr};
+
+    fn example() -> Map {
+        let input_txt = readme::example(include_str!("../README.md"), "For example:").unwrap();
+        Map::from_str(&input_txt).unwrap()
+    }
+
+    #[test]
+    fn test_frames() {
+        let map_unvisited = example();
+
+        // the start, then every step until the guard walks off after 54
+        let export = Export::default();
+        let mut last = None;
+        let count = export
+            .frames(&map_unvisited, |image| {
+                last = Some(image.clone());
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(1 + 54, count);
+        let last = last.unwrap();
+        assert_eq!((40, 40), (last.width, last.height));
+        // 41 positions visited, the guard is standing on the last one
+        let cells = |colour| last.pixels.iter().filter(|&&p| p == colour).count() / 16;
+        assert_eq!((40, 1, 0), (cells(VISITED), cells(GUARD), cells(LOOP)));
+
+        // every 10th step, and the last
+        let export = Export {
+            stride: 10,
+            cell_size: 1,
+            ..Export::default()
+        };
+        assert_eq!(
+            1 + 5 + 1,
+            export.frames(&map_unvisited, |_| Ok(())).unwrap()
+        );
+
+        // option two from the puzzle: 19 steps to the loop, then 4 + 2 + 4 + 2 moves and 4 turns
+        let export = Export {
+            obstruction: Some((7, 6)),
+            cell_size: 1,
+            ..Export::default()
+        };
+        let mut last = None;
+        let count = export
+            .frames(&map_unvisited, |image| {
+                last = Some(image.clone());
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(1 + 35, count);
+        let last = last.unwrap();
+        assert_eq!(NEW_OBSTRUCTION, last.pixels[7 * 10 + 6]);
+        assert_eq!(VISITED, last.pixels[10 + 4]);
+        assert_eq!(LOOP, last.pixels[4 * 10 + 4]);
+        assert_eq!(LOOP, last.pixels[6 * 10 + 2]);
+
+        let export = Export {
+            obstruction: Some((6, 4)),
+            ..Export::default()
+        };
+        assert!(export.frames(&map_unvisited, |_| Ok(())).is_err());
+    }
+
+    #[test]
+    fn test_write() {
+        let map_unvisited = Map::from_str("#..\n^..\n").unwrap();
+        let export = Export {
+            cell_size: 2,
+            ..Export::default()
+        };
+        let mut images = Vec::new();
+        export
+            .frames(&map_unvisited, |image| {
+                images.push(image.clone());
+                Ok(())
+            })
+            .unwrap();
+        // start, turn, two moves
+        assert_eq!(4, images.len());
+
+        let mut ppm = Vec::new();
+        images[0].write_ppm(&mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n6 4\n255\n"));
+        assert_eq!(11 + 6 * 4 * 3, ppm.len());
+
+        let mut png_bytes = Vec::new();
+        images[3].write_png(&mut png_bytes).unwrap();
+        let mut decoder = png::Decoder::new(Cursor::new(png_bytes));
+        decoder.set_transformations(png::Transformations::EXPAND);
+        let mut reader = decoder.read_info().unwrap();
+        let mut rgb = vec![0; reader.output_buffer_size().unwrap()];
+        reader.next_frame(&mut rgb).unwrap();
+        assert_eq!(images[3].rgb(), rgb);
+
+        let mut gif_bytes = Vec::new();
+        assert_eq!(4, export.write_gif(&map_unvisited, &mut gif_bytes).unwrap());
+        let mut decoder = gif::Decoder::new(Cursor::new(gif_bytes)).unwrap();
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((6, 4), (frame.width, frame.height));
+            frames += 1;
+        }
+        assert_eq!(4, frames);
+    }
+}
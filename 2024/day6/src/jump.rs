@@ -0,0 +1,456 @@
+//! Turn-to-turn simulation: the guard jumps straight to the cell in front of the next obstruction
+
+use crate::{Guard, Map, Orientation, Outcome};
+use std::collections::HashMap;
+
+/// marks a stop at the edge of the map, the guard walks off from there
+const EDGE: u32 = 1 << 31;
+
+/// Where the guard stops when walking from each cell in each orientation
+///
+/// a stop is the last free cell before an obstruction, or before the edge of the map
+#[derive(Clone, Debug)]
+pub struct JumpTable {
+    rows: usize,
+    cols: usize,
+    blocked: Vec<bool>,
+    /// indexed by `cell * 4 + orientation`, cell index of the stop (`| EDGE` at the map edge)
+    stops: Vec<u32>,
+}
+
+impl JumpTable {
+    pub fn new(map: &Map) -> Self {
+        let (rows, cols) = (map.rows(), map.cols());
+        let mut table = Self {
+            rows,
+            cols,
+            blocked: map
+                .coords()
+                .map(|coord| map.is_obstruction(coord))
+                .collect(),
+            stops: vec![0; rows * cols * 4],
+        };
+        for rr in 0..rows {
+            table.rescan_row(rr);
+        }
+        for cc in 0..cols {
+            table.rescan_col(cc);
+        }
+        table
+    }
+
+    /// add or remove an obstruction, only its row and column are rescanned
+    pub fn set_obstruction(&mut self, (row, col): (usize, usize), obstruction: bool) {
+        self.blocked[row * self.cols + col] = obstruction;
+        self.rescan_row(row);
+        self.rescan_col(col);
+    }
+
+    fn stop_index(&self, row: usize, col: usize, orientation: Orientation) -> usize {
+        (row * self.cols + col) * 4 + orientation as usize
+    }
+
+    /// recompute the Left and Right stops of a row
+    fn rescan_row(&mut self, row: usize) {
+        let cell = |cc: usize| (row * self.cols + cc) as u32;
+
+        // walking right: stop before the next obstruction to the right
+        let mut stop = cell(self.cols - 1) | EDGE;
+        for cc in (0..self.cols).rev() {
+            let idx = self.stop_index(row, cc, Orientation::Right);
+            if self.blocked[cell(cc) as usize] {
+                // never read, the guard can't stand on an obstruction
+                self.stops[idx] = 0;
+                stop = cell(cc.saturating_sub(1));
+            } else {
+                self.stops[idx] = stop;
+            }
+        }
+
+        // walking left
+        let mut stop = cell(0) | EDGE;
+        for cc in 0..self.cols {
+            let idx = self.stop_index(row, cc, Orientation::Left);
+            if self.blocked[cell(cc) as usize] {
+                self.stops[idx] = 0;
+                stop = cell((cc + 1).min(self.cols - 1));
+            } else {
+                self.stops[idx] = stop;
+            }
+        }
+    }
+
+    /// recompute the Up and Down stops of a column
+    fn rescan_col(&mut self, col: usize) {
+        let cell = |rr: usize| (rr * self.cols + col) as u32;
+
+        // walking down: stop above the next obstruction below
+        let mut stop = cell(self.rows - 1) | EDGE;
+        for rr in (0..self.rows).rev() {
+            let idx = self.stop_index(rr, col, Orientation::Down);
+            if self.blocked[cell(rr) as usize] {
+                self.stops[idx] = 0;
+                stop = cell(rr.saturating_sub(1));
+            } else {
+                self.stops[idx] = stop;
+            }
+        }
+
+        // walking up
+        let mut stop = cell(0) | EDGE;
+        for rr in 0..self.rows {
+            let idx = self.stop_index(rr, col, Orientation::Up);
+            if self.blocked[cell(rr) as usize] {
+                self.stops[idx] = 0;
+                stop = cell((rr + 1).min(self.rows - 1));
+            } else {
+                self.stops[idx] = stop;
+            }
+        }
+    }
+
+    /// where the guard stops, and whether it then walks off the map rather than turning
+    pub fn stop(&self, guard: &Guard) -> ((usize, usize), bool) {
+        let stop = self.stops[self.stop_index(guard.row, guard.col, guard.orientation)];
+        let cell = (stop & !EDGE) as usize;
+        ((cell / self.cols, cell % self.cols), stop & EDGE != 0)
+    }
+
+    /// patrol from `start` one jump at a time, with the same outcome as [`Map::patrol`]
+    ///
+    /// a loop is only noticed when a turn repeats, so a `budget` can run out on a loop that the
+    /// single-step patrol would already have reported
+    pub fn patrol(&self, start: &Guard, budget: Option<usize>) -> Jumps {
+        let mut turns = vec![(0, start.clone())];
+        // step at which the guard first left each (cell, orientation) after a turn
+        let mut seen = HashMap::new();
+        seen.insert((start.row, start.col, start.orientation), 0);
+
+        let mut step = 0;
+        let mut guard = start.clone();
+        let outcome = loop {
+            let ((row, col), edge) = self.stop(&guard);
+            let moves = guard.row.abs_diff(row) + guard.col.abs_diff(col);
+
+            if edge {
+                // walking off the map doesn't count as a step
+                let steps = step + moves;
+                break match budget {
+                    Some(b) if steps >= b => Outcome::BudgetExhausted { steps: b },
+                    _ => Outcome::Exited { steps },
+                };
+            }
+
+            // turning does
+            step += moves + 1;
+            if let Some(b) = budget.filter(|&b| step > b) {
+                break Outcome::BudgetExhausted { steps: b };
+            }
+
+            guard = Guard::new(row, col, guard.orientation.turn_right());
+            turns.push((step, guard.clone()));
+
+            if let Some(first) = seen.insert((row, col, guard.orientation), step) {
+                let length = step - first;
+                // once the guard is in the loop it stays there, so look for the first step that
+                // comes back `length` steps later
+                let (mut lo, mut hi) = (0, first);
+                while lo < hi {
+                    let mid = (lo + hi) / 2;
+                    if state_at(&turns, mid) == state_at(&turns, mid + length) {
+                        hi = mid;
+                    } else {
+                        lo = mid + 1;
+                    }
+                }
+                break Outcome::Loop { start: lo, length };
+            }
+        };
+
+        let mut jumps = Jumps { outcome, turns };
+        let end = jumps.end();
+        jumps.turns.retain(|(s, _)| *s <= end);
+        jumps
+    }
+
+    /// whether the guard, starting in state `from`, ends up in a loop, with an obstruction
+    /// `added` to the table's if there is one
+    ///
+    /// only that, so nothing but the turns is noted: a loop repeats a turn, and a state is just
+    /// an index into the stops. The added obstruction is checked on each jump rather than
+    /// scanned into the stops, so trying one costs nothing up front.
+    pub fn loops(&self, from: &Guard, added: Option<(usize, usize)>, seen: &mut Seen) -> bool {
+        seen.walk = seen.walk.wrapping_add(1);
+        if seen.walk == 0 {
+            // marks from 2^32 walks ago would match again
+            seen.walks.fill(0);
+            seen.walk = 1;
+        }
+
+        let mut idx = self.stop_index(from.row, from.col, from.orientation);
+        loop {
+            let orientation = Orientation::ALL[idx % 4];
+            let mut stop = self.stops[idx];
+            if let Some(before) = added.and_then(|coord| self.stop_before(idx / 4, stop, coord)) {
+                stop = before;
+            }
+            if stop & EDGE != 0 {
+                return false;
+            }
+            idx = stop as usize * 4 + orientation.turn_right() as usize;
+            if std::mem::replace(&mut seen.walks[idx], seen.walk) == seen.walk {
+                return true;
+            }
+        }
+    }
+
+    /// the stop in front of `(row, col)` if it is on the way from `cell` to `stop`, facing the
+    /// orientation that `stop` was looked up for
+    fn stop_before(&self, cell: usize, stop: u32, (row, col): (usize, usize)) -> Option<u32> {
+        let (r0, c0) = (cell / self.cols, cell % self.cols);
+        let end = (stop & !EDGE) as usize;
+        let (r1, c1) = (end / self.cols, end % self.cols);
+        // a stop in the same cell is a turn on the spot, there is nothing on the way
+        let on_the_way = if c0 == c1 && col == c0 {
+            (r0.min(r1)..=r0.max(r1)).contains(&row) && row != r0
+        } else if r0 == r1 && row == r0 {
+            (c0.min(c1)..=c0.max(c1)).contains(&col) && col != c0
+        } else {
+            false
+        };
+        on_the_way.then(|| {
+            // one step back towards `cell`
+            let (r, c) = (
+                if row == r0 {
+                    row
+                } else if row < r0 {
+                    row + 1
+                } else {
+                    row - 1
+                },
+                if col == c0 {
+                    col
+                } else if col < c0 {
+                    col + 1
+                } else {
+                    col - 1
+                },
+            );
+            (r * self.cols + c) as u32
+        })
+    }
+}
+
+/// The turns a [`JumpTable::loops`] walk has made, kept between walks so that it's never cleared
+#[derive(Clone, Debug)]
+pub struct Seen {
+    /// indexed like the stops, the walk that last turned into each (cell, orientation)
+    walks: Vec<u32>,
+    /// the current walk, marks from earlier walks don't match it
+    walk: u32,
+}
+
+impl Seen {
+    pub fn new(table: &JumpTable) -> Self {
+        Self {
+            walks: vec![0; table.stops.len()],
+            walk: 0,
+        }
+    }
+}
+
+/// Result of a jump patrol: how it ended and the turns along the way
+#[derive(Clone, Debug, PartialEq)]
+pub struct Jumps {
+    pub outcome: Outcome,
+    /// step and state after each turn, starting with the guard's start at step 0
+    pub turns: Vec<(usize, Guard)>,
+}
+
+impl Jumps {
+    /// last step of the patrol, when a loop is first closed for [`Outcome::Loop`]
+    pub fn end(&self) -> usize {
+        match self.outcome {
+            Outcome::Exited { steps } | Outcome::BudgetExhausted { steps } => steps,
+            Outcome::Loop { start, length } => start + length,
+        }
+    }
+
+    /// the guard's state after `step` steps
+    pub fn state_at(&self, step: usize) -> Guard {
+        state_at(&self.turns, step)
+    }
+}
+
+/// the guard's state after `step` steps, walking straight on from the last turn before it
+fn state_at(turns: &[(usize, Guard)], step: usize) -> Guard {
+    let idx = turns.partition_point(|(s, _)| *s <= step) - 1;
+    let (turned_at, guard) = &turns[idx];
+    let moves = (step - turned_at) as isize;
+    let (dr, dc) = guard.orientation.delta();
+    Guard::new(
+        guard.row.wrapping_add_signed(dr * moves),
+        guard.col.wrapping_add_signed(dc * moves),
+        guard.orientation,
+    )
+}
+
+impl Map {
+    /// replay a jump patrol of this map: mark every state and leave the guard where it ended
+    ///
+    /// the positions visited come out the same as after [`Map::patrol`]
+    pub fn replay(&mut self, jumps: &Jumps) {
+        let end = jumps.end();
+        for (idx, (turned_at, guard)) in jumps.turns.iter().enumerate() {
+            let until = jumps.turns.get(idx + 1).map_or(end, |(s, _)| s - 1);
+            self.mark(guard);
+            let (dr, dc) = guard.orientation.delta();
+            for moves in 1..=(until - turned_at) as isize {
+                let r = guard.row.wrapping_add_signed(dr * moves);
+                let c = guard.col.wrapping_add_signed(dc * moves);
+                self.mark(&Guard::new(r, c, guard.orientation));
+            }
+        }
+        self.guard = jumps.state_at(end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::jump::*;
+    use aoc_common::readme;
+    use std::str::FromStr;
+
+    fn example() -> Map {
+        let input_txt = readme::example(include_str!("../README.md"), "For example:").unwrap();
+        Map::from_str(&input_txt).unwrap()
+    }
+
+    #[test]
+    fn test_set_obstruction() {
+        let map_unvisited = example();
+        let mut table = JumpTable::new(&map_unvisited);
+
+        let mut blocked = map_unvisited.clone();
+        blocked.set_obstruction((6, 3), true);
+        table.set_obstruction((6, 3), true);
+        assert_eq!(JumpTable::new(&blocked).stops, table.stops);
+
+        table.set_obstruction((6, 3), false);
+        assert_eq!(JumpTable::new(&map_unvisited).stops, table.stops);
+
+        // start facing up at (6, 4): stop below the obstruction at (0, 4)
+        assert_eq!(((1, 4), false), table.stop(&map_unvisited.guard));
+        assert_eq!(
+            ((9, 4), true),
+            table.stop(&Guard::new(6, 4, Orientation::Down))
+        );
+    }
+
+    #[test]
+    fn test_patrol() {
+        let map_unvisited = example();
+
+        // same outcome, visits and final guard as the single-step patrol, for every obstruction
+        for coord in map_unvisited.coords() {
+            let mut single = map_unvisited.clone();
+            single.set_obstruction(coord, coord != (6, 4));
+            let table = JumpTable::new(&single);
+
+            for budget in [None, Some(0), Some(6), Some(40)] {
+                let mut single = single.clone();
+                let outcome = single.patrol(budget);
+
+                let jumps = table.patrol(&map_unvisited.guard, budget);
+                if budget.is_none() || !matches!(outcome, Outcome::Loop { .. }) {
+                    assert_eq!(outcome, jumps.outcome, "{coord:?} {budget:?}");
+
+                    let mut replayed = map_unvisited.clone();
+                    replayed.set_obstruction(coord, coord != (6, 4));
+                    replayed.replay(&jumps);
+                    assert_eq!(single.guard, replayed.guard, "{coord:?} {budget:?}");
+                    assert_eq!(
+                        single.directions, replayed.directions,
+                        "{coord:?} {budget:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_loops() {
+        let map_unvisited = example();
+        let mut table = JumpTable::new(&map_unvisited);
+        let mut seen = Seen::new(&table);
+
+        // same as the single-step patrol for every obstruction, added to the walk or to the
+        // table, with one `Seen` for all of them
+        for coord in map_unvisited.coords() {
+            if coord == (6, 4) || map_unvisited.is_obstruction(coord) {
+                continue;
+            }
+            let mut single = map_unvisited.clone();
+            single.set_obstruction(coord, true);
+            let looped = matches!(single.patrol(None), Outcome::Loop { .. });
+
+            let from = &map_unvisited.guard;
+            assert_eq!(
+                looped,
+                table.loops(from, Some(coord), &mut seen),
+                "{coord:?}"
+            );
+            table.set_obstruction(coord, true);
+            assert_eq!(looped, table.loops(from, None, &mut seen), "{coord:?}");
+            table.set_obstruction(coord, false);
+        }
+
+        // from a state on a loop, and then from the same state without it
+        let on_loop = Guard::new(6, 4, Orientation::Left);
+        assert!(table.loops(&on_loop, Some((6, 3)), &mut seen));
+        assert!(!table.loops(&on_loop, None, &mut seen));
+
+        // from states all over the map, with an obstruction added in front of the guard, where
+        // it came from, or anywhere else
+        for from in map_unvisited
+            .coords()
+            .flat_map(|(r, c)| Orientation::ALL.map(|orientation| Guard::new(r, c, orientation)))
+        {
+            if map_unvisited.is_obstruction((from.row, from.col)) {
+                continue;
+            }
+            for added in [(6, 3), (from.row, 4), (2, from.col), (9, 9)] {
+                if added == (from.row, from.col) || map_unvisited.is_obstruction(added) {
+                    continue;
+                }
+                let mut single = map_unvisited.clone();
+                single.guard = from.clone();
+                single.set_obstruction(added, true);
+                let looped = matches!(single.patrol(None), Outcome::Loop { .. });
+                assert_eq!(
+                    looped,
+                    table.loops(&from, Some(added), &mut seen),
+                    "{from:?} {added:?}"
+                );
+            }
+        }
+
+        // turning on the spot
+        let boxed_in = Map::from_str(".#.\n#^#\n.#.\n").unwrap();
+        let table = JumpTable::new(&boxed_in);
+        let mut seen = Seen::new(&table);
+        assert!(table.loops(&boxed_in.guard, None, &mut seen));
+        // with something added off its way
+        assert!(table.loops(&boxed_in.guard, Some((2, 2)), &mut seen));
+
+        // marks from before the count of walks wraps round don't match the walks after it
+        let table = JumpTable::new(&map_unvisited);
+        let mut seen = Seen {
+            walks: vec![1; table.stops.len()],
+            walk: u32::MAX,
+        };
+        assert!(!table.loops(&map_unvisited.guard, None, &mut seen));
+        assert_eq!(1, seen.walk);
+    }
+}
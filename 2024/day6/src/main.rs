@@ -1,571 +1,305 @@
 use anyhow::Result;
-use std::error::Error;
+use aoc_common::{input::InputArgs, parallel, solution::Solution};
+use clap::{Parser, ValueEnum};
+use day6::{
+    Day6, Outcome, Search,
+    anatomy::{Summary, loop_anatomy},
+    export::{Export, Format},
+    loop_obstructions,
+    optimise::{Extreme, extreme_obstruction, fewest_to_free, fewest_to_trap},
+    rules::{Edge, Rules, Turn, loop_obstructions_with},
+    squad::{Squad, Trap},
+};
+use std::{path::PathBuf, process::ExitCode, str::FromStr};
+
+/// how to draw the map before solving
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Render {
+    /// the guard's route in the puzzle's `|`, `-`, `+` notation
+    Path,
+    /// how many ways the guard faced at each position, in ANSI colours
+    Heatmap,
+    /// the guard's route with `O` at every position where an obstruction makes a loop
+    Loops,
+}
 
-/// direction that the guard is facing
-#[derive(Clone, Debug, PartialEq)]
-pub enum Orientation {
-    Up,
+/// which way the guard turns at an obstruction
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum TurnArg {
+    /// the puzzle's rule
     Right,
-    Down,
     Left,
+    Around,
+    /// right, then left, then right, ...
+    Alternate,
+    /// pseudo-random right, left or around, see --seed
+    Random,
 }
 
-impl Iterator for Orientation {
-    type Item = Orientation;
-
-    /// enums don't have a built-in iterator (for algebraic type theory reasons)
-    fn next(&mut self) -> Option<Self::Item> {
-        match self {
-            Orientation::Up => Some(Self::Item::Right),
-            Orientation::Right => Some(Self::Item::Down),
-            Orientation::Down => Some(Self::Item::Left),
-            Orientation::Left => Some(Self::Item::Up),
-        }
-    }
+/// what to look for with --optimise
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Query {
+    /// the fewest new obstructions that trap the guard
+    Trap,
+    /// the fewest obstructions to take away so that a trapped guard gets out
+    Free,
+    /// the new obstruction that makes the guard visit the most positions
+    Most,
+    /// the new obstruction that makes the guard visit the fewest positions
+    Fewest,
 }
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct Guard {
-    orientation: Orientation,
-    row: usize,
-    col: usize,
-}
-impl Guard {
-    fn new(rr: usize, cc: usize, orientation: Orientation) -> Self {
-        Self {
-            orientation: orientation,
-            row: rr,
-            col: cc,
-        }
-    }
+/// what the edge of the map does to the guard
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum EdgeArg {
+    /// the puzzle's rule, the guard walks off the map
+    Exit,
+    /// the guard comes back on at the opposite edge
+    Wrap,
+    /// the edge blocks the guard like an obstruction
+    Wall,
 }
 
-/// Metadata and State of a position (within the grid)
-#[derive(Clone, Copy, Debug)]
-struct PositionMetadataState {
-    obstruction: bool,
-    visited: u32,
-}
-impl PositionMetadataState {
-    fn new(obs: bool) -> Self {
-        Self {
-            obstruction: obs,
-            visited: 0,
-        }
-    }
-}
+/// solve day 6, optionally drawing the guard's route
+#[derive(Debug, Parser)]
+struct Cli {
+    #[command(flatten)]
+    input: InputArgs,
 
-/// Not to be confused with the generic std::Map datastructure, naming is hard 😭
-#[derive(Clone, Debug)]
-pub struct Map<const ROWS: usize, const COLS: usize> {
-    /// coordinate grid, with metadata and state about each position
-    grid: [[PositionMetadataState; COLS]; ROWS],
-    /// state to track the guard
-    guard: Guard,
-}
-impl<const ROWS: usize, const COLS: usize> Map<ROWS, COLS> {
-    /// general constructor
-    pub fn from_str(input_txt: &str) -> Self {
-        let mut g: Option<Guard> = None;
-        let mut result: [[Option<PositionMetadataState>; COLS]; ROWS] = [[None; COLS]; ROWS];
-
-        for (rr, row) in input_txt.lines().enumerate() {
-            for (cc, col) in row.chars().enumerate() {
-                let obs = col == '#';
-                if col == '^' {
-                    g = Some(Guard::new(rr, cc, Orientation::Up));
-                }
-                result[rr][cc] = Some(PositionMetadataState::new(obs));
-            }
-        }
+    /// step through the patrol in the terminal instead of solving
+    #[arg(short, long, conflicts_with_all = ["render", "export", "turn", "edge"])]
+    debug: bool,
 
-        Map {
-            grid: result.map(|r| r.map(|p| p.unwrap())),
-            guard: g.unwrap(),
-        }
-    }
+    /// solve with every guard on the map patrolling at once, reporting where they meet
+    #[arg(short, long, conflicts_with_all = ["debug", "anatomy", "render", "turn", "edge", "trace", "export"])]
+    guards: bool,
 
-    /// return (row, col) in the Map of whatever is "in front" of the guard
-    fn coord_in_front_of_guard(&self) -> Option<(usize, usize)> {
-        match self.guard.orientation {
-            Orientation::Up => {
-                if self.guard.row > 0 {
-                    let new_row = self.guard.row - 1;
-                    let new_col = self.guard.col;
-                    Some((new_row, new_col))
-                } else {
-                    None
-                }
-            }
-            Orientation::Right => {
-                if self.guard.col < COLS - 1 {
-                    let new_row = self.guard.row;
-                    let new_col = self.guard.col + 1;
-                    Some((new_row, new_col))
-                } else {
-                    None
-                }
-            }
-            Orientation::Down => {
-                if self.guard.row < ROWS - 1 {
-                    let new_row = self.guard.row + 1;
-                    let new_col = self.guard.col;
-                    Some((new_row, new_col))
-                } else {
-                    None
-                }
-            }
-            Orientation::Left => {
-                if self.guard.col > 0 {
-                    let new_row = self.guard.row;
-                    let new_col = self.guard.col - 1;
-                    Some((new_row, new_col))
-                } else {
-                    None
-                }
-            }
-        }
-    }
+    /// after solving, describe the loops from part 2: their cycle lengths and lead-ins
+    #[arg(short, long)]
+    anatomy: bool,
 
-    /// didn't really need a count of times visited, so this just reduces the count
-    pub fn count_positions_visited(&self) -> usize {
-        self.grid
-            .as_flattened()
-            .iter()
-            .filter(|p| p.visited > 0)
-            .count()
-    }
+    /// search for the obstructions to add or take away, instead of solving
+    #[arg(short, long, conflicts_with_all = ["guards", "turn", "edge"])]
+    optimise: Option<Query>,
 
-    fn iter_visited<'a>(&'a self) -> impl Iterator<Item = (usize, usize)> + 'a {
-        VisitedMapIter::<'a, ROWS, COLS> {
-            map: self,
-            next_state: None,
-        }
-    }
-}
+    /// most obstructions that --optimise trap or free may change
+    #[arg(long, default_value_t = 4, requires = "optimise")]
+    max_changes: usize,
 
-impl<const ROWS: usize, const COLS: usize> Iterator for &mut Map<ROWS, COLS> {
-    type Item = Guard;
+    /// draw the map to stdout first
+    #[arg(short, long, conflicts_with_all = ["turn", "edge"])]
+    render: Option<Render>,
 
-    /// next action for the guard: step, turn or done
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.coord_in_front_of_guard() {
-            Some((r, c)) => {
-                if self.grid[r][c].obstruction {
-                    self.guard.orientation = self.guard.orientation.next().unwrap();
-                } else {
-                    self.grid[r][c].visited += 1;
-                    self.guard.row = r;
-                    self.guard.col = c;
-                }
-                Some(self.guard.clone())
-            }
-            None => None,
-        }
-    }
-}
+    /// solve with the guard turning another way (slower, a step at a time)
+    #[arg(long, value_enum, default_value = "right")]
+    turn: TurnArg,
 
-/// State for iterating over visited coords in Map
-#[derive(Clone)]
-struct VisitedMapIter<'a, const ROWS: usize, const COLS: usize> {
-    map: &'a Map<ROWS, COLS>,
-    /// bookkeeping for iterator
-    next_state: Option<(usize, usize)>,
-}
+    /// solve with the map's edge wrapping round or walled in (slower, a step at a time)
+    #[arg(long, value_enum, default_value = "exit")]
+    edge: EdgeArg,
 
-impl<'a, const ROWS: usize, const COLS: usize> Iterator for VisitedMapIter<'a, ROWS, COLS> {
-    type Item = (usize, usize);
-
-    /// return next visited coord
-    fn next(&mut self) -> Option<Self::Item> {
-        // helper function
-        fn inc<const ROWS: usize, const COLS: usize>(r: usize, c: usize) -> Option<(usize, usize)> {
-            if c < COLS - 1 {
-                Some((r, c + 1))
-            } else if r < ROWS - 1 {
-                Some((r + 1, 0))
-            } else {
-                None
-            }
-        }
+    /// seed for `--turn random`
+    #[arg(long, default_value_t = 1)]
+    seed: u8,
 
-        let (start_r, mut start_c) = if self.next_state.is_none() {
-            (0, 0)
-        } else {
-            self.next_state.unwrap()
-        };
+    /// write the guard's patrol as its turns, to a .json or .csv file
+    #[arg(short, long, value_name = "FILE", conflicts_with_all = ["turn", "edge"])]
+    trace: Option<PathBuf>,
 
-        // search for next visited
-        for row in start_r..ROWS {
-            for col in start_c..COLS {
-                if self.map.grid[row][col].visited > 0 {
-                    self.next_state = inc::<ROWS, COLS>(row, col);
-                    // dbg!(Some((row, col)));
-                    return Some((row, col));
-                }
-                self.next_state = inc::<ROWS, COLS>(row, col);
-                // dbg!(row, col);
-            }
-            start_c = 0;
-        }
+    /// write image frames of the patrol, to a directory (ppm, png) or a file (gif)
+    #[arg(short, long, value_name = "PATH", conflicts_with_all = ["turn", "edge"])]
+    export: Option<PathBuf>,
 
-        // didn't find any visited positions
-        None
-    }
-}
-
-#[cfg(test)]
-mod tests {
-
-    use crate::*;
-
-    #[test]
-    fn test_1() {
-        const FINAL_ANSWER: usize = 41;
-
-        const ARY_ROWS_COLS: usize = 10;
-        let tmp: String = String::from(
-            //123456789
-            "....#.....\n\
-             .........#\n\
-             ..........\n\
-             ..#.......\n\
-             .......#..\n\
-             ..........\n\
-             .#..^.....\n\
-             ........#.\n\
-             #.........\n\
-             ......#...",
-        );
-        let input_txt = tmp.as_str();
+    /// image format of the exported frames
+    #[arg(long, value_enum, default_value = "gif", requires = "export")]
+    format: Format,
 
-        let mut m = Map::<ARY_ROWS_COLS, ARY_ROWS_COLS>::from_str(input_txt);
+    /// pixels along each side of a map position in the exported frames
+    #[arg(long, default_value_t = 4, requires = "export")]
+    cell_size: usize,
 
-        assert_eq!(
-            Guard::new(5, 4, Orientation::Up),
-            m.into_iter().next().unwrap()
-        );
-        assert_eq!(
-            Guard::new(4, 4, Orientation::Up),
-            m.into_iter().next().unwrap()
-        );
+    /// steps between exported frames
+    #[arg(long, default_value_t = 1, requires = "export")]
+    stride: usize,
 
-        let mut distinct_positions_visited = m.count_positions_visited();
-        assert_eq!(2, distinct_positions_visited);
-
-        // check the first several steps
-        assert_eq!(
-            Guard::new(3, 4, Orientation::Up),
-            m.into_iter().next().unwrap()
-        );
-        assert_eq!(
-            Guard::new(2, 4, Orientation::Up),
-            m.into_iter().next().unwrap()
-        );
-        assert_eq!(
-            Guard::new(1, 4, Orientation::Up),
-            m.into_iter().next().unwrap()
-        );
-
-        // check the first turn
-        assert_eq!(
-            Guard::new(1, 4, Orientation::Right),
-            m.into_iter().next().unwrap()
-        );
-        assert_eq!(
-            Guard::new(1, 5, Orientation::Right),
-            m.into_iter().next().unwrap()
-        );
-        assert_eq!(
-            Guard::new(1, 6, Orientation::Right),
-            m.into_iter().next().unwrap()
-        );
-        assert_eq!(
-            Guard::new(1, 7, Orientation::Right),
-            m.into_iter().next().unwrap()
-        );
-
-        distinct_positions_visited = m.count_positions_visited();
-        assert_eq!(8, distinct_positions_visited);
+    /// add an obstruction before exporting, to show the loop it makes
+    #[arg(long, value_name = "ROW,COL", value_parser = parse_coord, requires = "export")]
+    obstruction: Option<(usize, usize)>,
+}
 
-        // keep going
-        for nstep in &mut m {
-            dbg!(nstep);
-        }
+/// `ROW,COL` from the command line, counting from 0
+fn parse_coord(arg: &str) -> Result<(usize, usize)> {
+    let (row, col) = arg
+        .split_once(',')
+        .ok_or_else(|| anyhow::anyhow!("expected ROW,COL"))?;
+    Ok((row.trim().parse()?, col.trim().parse()?))
+}
 
-        distinct_positions_visited = m.count_positions_visited();
+fn main() -> ExitCode {
+    aoc_common::report::exit_code(run())
+}
 
-        assert!(ARY_ROWS_COLS * ARY_ROWS_COLS > distinct_positions_visited);
-        assert_eq!(FINAL_ANSWER, distinct_positions_visited);
+fn run() -> Result<()> {
+    let cli = Cli::parse();
+    let input_txt = cli.input.load(&day6::INPUT)?;
+    if cli.guards {
+        return solve_squad(&input_txt);
     }
+    let input = Day6::parse(&input_txt)?;
 
-    #[test]
-    fn test_2() {
-        const PARTIAL_ANSWER: usize = 41;
-
-        const ARY_ROWS_COLS: usize = 10;
-        let tmp: String = String::from(
-            //123456789
-            "....#.....\n\
-             .........#\n\
-             ..........\n\
-             ..#.......\n\
-             .......#..\n\
-             ..........\n\
-             .#..^.....\n\
-             ........#.\n\
-             #.........\n\
-             ......#...",
-        );
-        let input_txt = tmp.as_str();
-
-        let mut m = Map::<ARY_ROWS_COLS, ARY_ROWS_COLS>::from_str(input_txt);
-
-        // update m with patrol coverage
-        for _nstep in &mut m {
-            // dbg!(nstep);
-        }
-
-        let distinct_positions_visited = m.count_positions_visited();
-
-        assert!(ARY_ROWS_COLS * ARY_ROWS_COLS > distinct_positions_visited);
-        assert_eq!(PARTIAL_ANSWER, distinct_positions_visited);
-
-        // test iterating over visited coords
-        let cm = m.clone();
-
-        let mut iter = cm.iter_visited();
-        assert_eq!((1, 4), iter.next().unwrap());
-        assert_eq!((1, 5), iter.next().unwrap());
-        assert_eq!((1, 6), iter.next().unwrap());
-        assert_eq!((1, 7), iter.next().unwrap());
-        assert_eq!((1, 8), iter.next().unwrap());
-        assert_eq!((2, 4), iter.next().unwrap());
-
-        let distinct_positions_visited = cm.count_positions_visited();
-        assert_eq!(distinct_positions_visited, cm.iter_visited().count());
+    if cli.debug {
+        return day6::debugger::interactive(&input);
     }
 
-    #[test]
-    fn test_3() {
-        const PARTIAL_ANSWER: usize = 41;
-        const FINAL_ANSWER: usize = 1;
-
-        const ARY_ROWS_COLS: usize = 10;
-        let tmp: String = String::from(
-            //123456789
-            "....#.....\n\
-             .........#\n\
-             ..........\n\
-             ..#.......\n\
-             .......#..\n\
-             ..........\n\
-             .#..^.....\n\
-             ........#.\n\
-             #.........\n\
-             ......#...",
-        );
-        let input_txt = tmp.as_str();
-
-        let mut m = Map::<ARY_ROWS_COLS, ARY_ROWS_COLS>::from_str(input_txt);
-
-        let map_start = m.clone();
-
-        // update m with patrol coverage
-        for _nstep in &mut m {
-            // dbg!(nstep);
+    match cli.render {
+        Some(Render::Path) => print!("{}", input.render_path(&[])),
+        Some(Render::Heatmap) => {
+            let mut map_visited = input.clone();
+            for _step in &mut map_visited {}
+            print!("{}", map_visited.render_heatmap());
         }
-
-        let distinct_positions_visited = m.count_positions_visited();
-
-        assert!(ARY_ROWS_COLS * ARY_ROWS_COLS > distinct_positions_visited);
-        assert_eq!(PARTIAL_ANSWER, distinct_positions_visited);
-
-        let mut count_infinite_loops = 0;
-        let coord = (6, 3);
-
-        dbg!(coord);
-
-        // on tmp Map:
-        //   change coord to be an obstruction
-        //   check if guard loops
-
-        let mut sim = map_start.clone();
-        sim.grid[coord.0][coord.1].obstruction = true;
-
-        dbg! {&sim.guard};
-
-        for guard_step in &mut sim {
-            println!("{:?}", &guard_step);
-
-            if map_start.guard == guard_step {
-                count_infinite_loops += 1;
-                println!(
-                    " -- FOUND infinite loop with obstruction at [{}][{}]",
-                    coord.0, coord.1
-                );
-                break;
-            }
+        Some(Render::Loops) => {
+            let loops = loop_obstructions(&input, parallel::threads(), Search::FromPrefix);
+            print!("{}", input.render_path(&loops));
         }
-
-        dbg!(sim.grid[coord.0][coord.1]);
-        println!(
-            " -- NO infinite loop with obstruction at [{}][{}]",
-            coord.0, coord.1
-        );
-
-        assert_eq!(FINAL_ANSWER, count_infinite_loops);
+        None => {}
     }
 
-    #[test]
-    fn test_4() {
-        const PARTIAL_ANSWER: usize = 41;
-        const FINAL_ANSWER: usize = 6;
-
-        const ARY_ROWS_COLS: usize = 10;
-        let tmp: String = String::from(
-            //123456789
-            "....#.....\n\
-             .........#\n\
-             ..........\n\
-             ..#.......\n\
-             .......#..\n\
-             ..........\n\
-             .#..^.....\n\
-             ........#.\n\
-             #.........\n\
-             ......#...",
-        );
-        let input_txt = tmp.as_str();
-
-        let map_unvisited = Map::<ARY_ROWS_COLS, ARY_ROWS_COLS>::from_str(input_txt);
-
-        let mut map_visited = map_unvisited.clone();
+    if let Some(path) = &cli.trace {
+        input.trace(None).write(path)?;
+        println!("wrote the trace to {}", path.display());
+    }
 
-        // update m with patrol coverage
-        for _nstep in &mut map_visited {
-            // dbg!(nstep);
-        }
+    if let Some(path) = &cli.export {
+        let export = Export {
+            format: cli.format,
+            cell_size: cli.cell_size,
+            stride: cli.stride,
+            obstruction: cli.obstruction,
+            ..Export::default()
+        };
+        let frames = export.write(&input, path)?;
+        println!("wrote {frames} frames to {}", path.display());
+    }
 
-        let distinct_positions_visited = map_visited.count_positions_visited();
-
-        assert!(ARY_ROWS_COLS * ARY_ROWS_COLS > distinct_positions_visited);
-        assert_eq!(PARTIAL_ANSWER, distinct_positions_visited);
-
-        let mut count_infinite_loops = 0;
-        for coord in map_visited
-            .clone()
-            .iter_visited()
-            .filter(|uu| (uu.0, uu.1) != (map_unvisited.guard.row, map_unvisited.guard.col))
-        {
-            dbg!(coord);
-
-            // on tmp Map:
-            //   change coord to be an obstruction
-            //   check if guard loops
-
-            let mut sim = map_unvisited.clone();
-            sim.grid[coord.0][coord.1].obstruction = true;
-
-            // record start position (optional) and turns
-            // NOTE: some infinite loops are a subset of the whole path; so they may not return to start position
-            // FIXME: this should be stored in the iterator struct and return something like Err<InfiniteLoop>
-            let mut previous_steps = Vec::<Guard>::new();
-            // previous_steps.push(sim.guard.clone()); // optimization ... some infnite loops return to start position
-            let mut prev_orientation: Orientation = sim.guard.orientation.clone();
-
-            for guard_step in &mut sim {
-                // retracing turn-step indicates that the guard is in a loop
-                if previous_steps.contains(&guard_step) {
-                    count_infinite_loops += 1;
-                    println!(
-                        " -- FOUND infinite loop with obstruction at [{}][{}]",
-                        coord.0, coord.1
-                    );
-                    break;
+    if let Some(query) = cli.optimise {
+        match query {
+            Query::Trap => match fewest_to_trap(&input, cli.max_changes) {
+                Some(added) => {
+                    println!("{} new obstructions trap the guard: {added:?}", added.len())
+                }
+                None => println!(
+                    "more than {} new obstructions to trap the guard",
+                    cli.max_changes
+                ),
+            },
+            Query::Free => match fewest_to_free(&input, cli.max_changes) {
+                Some(removed) => println!(
+                    "taking away {} obstructions frees the guard: {removed:?}",
+                    removed.len()
+                ),
+                None => println!(
+                    "more than {} obstructions to take away to free the guard",
+                    cli.max_changes
+                ),
+            },
+            Query::Most | Query::Fewest => {
+                let extreme = if query == Query::Most {
+                    Extreme::Most
                 } else {
-                    // optimization ... only record turns intead of every step
-                    if prev_orientation != guard_step.orientation {
-                        prev_orientation = guard_step.orientation.clone();
-                        previous_steps.push(guard_step);
-                    }
+                    Extreme::Fewest
+                };
+                match extreme_obstruction(&input, extreme) {
+                    Some((coord, count)) => println!(
+                        "a new obstruction at {coord:?}: {count} distinct positions visited"
+                    ),
+                    None => println!("every new obstruction on the guard's path traps it"),
                 }
             }
+        }
+        return Ok(());
+    }
 
+    let rules = Rules {
+        turn: match cli.turn {
+            TurnArg::Right => Turn::Right,
+            TurnArg::Left => Turn::Left,
+            TurnArg::Around => Turn::Around,
+            TurnArg::Alternate => Turn::Alternate,
+            TurnArg::Random => Turn::Random { seed: cli.seed },
+        },
+        edge: match cli.edge {
+            EdgeArg::Exit => Edge::Exit,
+            EdgeArg::Wrap => Edge::Wrap,
+            EdgeArg::Wall => Edge::Wall,
+        },
+        ..Rules::default()
+    };
+    let loops = if rules != Rules::default() {
+        let mut map_visited = input.clone();
+        let outcome = map_visited.patrol_with(&rules, None);
+        println!(
+            "part 1 distinct_positions_visited = {} ({outcome:?})",
+            map_visited.count_positions_visited()
+        );
+        let loops = loop_obstructions_with(&input, &rules, parallel::threads());
+        println!(
+            "part 2 count infinite loops from 1 new obstruction: {}",
+            loops.len()
+        );
+        if matches!(outcome, Outcome::Loop { .. }) {
             println!(
-                " -- NO infinite loop with obstruction at [{}][{}]",
-                coord.0, coord.1
-            )
+                "(the guard loops without a new obstruction, so every position on its path counts)"
+            );
         }
+        loops
+    } else {
+        let part1_distinct_positions_visited = Day6::part1(&input)?;
+        println!("part 1 distinct_positions_visited = {part1_distinct_positions_visited}");
 
-        assert_eq!(FINAL_ANSWER, count_infinite_loops);
+        // the loops themselves rather than Day6::part2's count, for --anatomy
+        let loops = loop_obstructions(&input, parallel::threads(), Search::FromPrefix);
+        println!(
+            "part 2 count infinite loops from 1 new obstruction: {}",
+            loops.len()
+        );
+        loops
+    };
+
+    if cli.anatomy {
+        let anatomies = loop_anatomy(&input, &rules, &loops, parallel::threads());
+        print!("{}", Summary::new(&anatomies));
     }
-}
 
-#[doc = include_str!("../README.md")]
-fn main() -> Result<(), Box<dyn Error>> {
-    // compile input file into binary
-    const INPUT_TXT: &str = include_str!("../input.txt");
-    const ARY_ROWS_COLS: usize = 130;
+    Ok(())
+}
 
-    let map_unvisited = Map::<ARY_ROWS_COLS, ARY_ROWS_COLS>::from_str(INPUT_TXT);
+/// both parts with every guard patrolling, and what each guard did
+fn solve_squad(input_txt: &str) -> Result<()> {
+    let squad_unvisited = Squad::from_str(input_txt)?;
+    let mut squad = squad_unvisited.clone();
+    let patrol = squad.patrol(None);
 
-    let mut map_visited = map_unvisited.clone();
-    // walk the guard through the map
-    for _step in &mut map_visited {
-        // dbg!(step);
+    for (idx, outcome) in patrol.outcomes.iter().enumerate() {
+        println!(
+            "guard {idx}: {} distinct positions ({outcome:?})",
+            squad.coverage(idx)
+        );
     }
-
-    let part1_distinct_positions_visited = map_visited.count_positions_visited();
-    println!("part 1 distinct_positions_visited = {part1_distinct_positions_visited}");
-
-    let mut part2_count_infinite_loops = Vec::<(usize, usize)>::new();
-    // NOTE: cannot put obstruction at the Guard's starting point!!!
-    for coord in map_visited
-        .clone()
-        .iter_visited()
-        .filter(|uu| (uu.0, uu.1) != (map_unvisited.guard.row, map_unvisited.guard.col))
-    {
-        // dbg!(coord);
-
-        // on tmp Map:
-        //   change coord to be an obstruction
-        //   check if guard loops
-
-        let mut sim = map_unvisited.clone();
-        sim.grid[coord.0][coord.1].obstruction = true;
-
-        // record start position (optional) and turns
-        // NOTE: some infinite loops are a subset of the whole path; so they may not return to start position
-        // FIXME: this should be stored in the iterator struct and return something like Err<InfiniteLoop>
-        let mut previous_steps = Vec::<Guard>::new();
-        previous_steps.push(sim.guard.clone()); // optimization ... some infnite loops return to start position
-
-        for guard_step in &mut sim {
-            // retracing turn-step indicates that the guard is in a loop
-            if previous_steps.contains(&guard_step) {
-                part2_count_infinite_loops.push(coord);
-                // println!(
-                //     " -- FOUND infinite loop with obstruction at [{}][{}]",
-                //     coord.0, coord.1
-                // );
-                break;
-            } else {
-                // optimization ... only record turns intead of every step
-                if previous_steps.last().unwrap().orientation != guard_step.orientation {
-                    // prev_orientation = guard_step.orientation.clone();
-                    previous_steps.push(guard_step);
-                }
-            }
-        }
+    for collision in &patrol.collisions {
+        println!("{collision:?}");
     }
 
-    // < 1656
-    // > 931
     println!(
-        "part 2 count infinite loops from 1 new obstruction: {}",
-        part2_count_infinite_loops.iter().count()
+        "part 1 distinct_positions_visited = {}",
+        squad.count_positions_visited()
     );
-
+    for trap in [Trap::Any, Trap::All] {
+        let loops = squad_unvisited.loop_obstructions(parallel::threads(), trap);
+        println!(
+            "part 2 count infinite loops from 1 new obstruction ({trap:?}): {}",
+            loops.len()
+        );
+    }
     Ok(())
 }
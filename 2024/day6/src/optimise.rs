@@ -0,0 +1,265 @@
+//! Searches for the obstructions that matter most: the fewest to add so that the guard is stuck,
+//! the fewest to take away so that it gets out, and the one new obstruction that makes its
+//! patrol cover the most or the fewest positions
+//!
+//! only obstructions the guard walks into (or positions it walks through) can change its patrol,
+//! so each search only branches on those
+
+use crate::{
+    Map, Outcome,
+    jump::{JumpTable, Jumps},
+};
+use std::collections::{HashSet, VecDeque};
+
+/// which end of [`extreme_obstruction`] to look for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Extreme {
+    Most,
+    Fewest,
+}
+
+/// the map with an obstruction added or removed at each of `changes`, after the guard has
+/// patrolled it
+fn patrol(map_unvisited: &Map, changes: &[(usize, usize)]) -> (Map, Jumps) {
+    let mut map = map_unvisited.clone();
+    for &coord in changes {
+        map.set_obstruction(coord, !map.is_obstruction(coord));
+    }
+    let jumps = JumpTable::new(&map).patrol(&map.guard, None);
+    map.replay(&jumps);
+    (map, jumps)
+}
+
+/// breadth-first over sets of changes, up to `max_changes` of them, for the first set whose
+/// patrol is a `goal`; `branch` says which changes could make a difference after a patrol
+///
+/// sets are tried smallest first, and in row-major order for the same size
+fn fewest_changes(
+    map_unvisited: &Map,
+    max_changes: usize,
+    goal: impl Fn(&Outcome) -> bool,
+    branch: impl Fn(&Map, &Jumps) -> Vec<(usize, usize)>,
+) -> Option<Vec<(usize, usize)>> {
+    let mut queue = VecDeque::from([Vec::new()]);
+    let mut seen: HashSet<Vec<(usize, usize)>> = HashSet::new();
+
+    while let Some(changes) = queue.pop_front() {
+        let (map_visited, jumps) = patrol(map_unvisited, &changes);
+        if goal(&jumps.outcome) {
+            return Some(changes);
+        }
+        if changes.len() == max_changes {
+            continue;
+        }
+        for coord in branch(&map_visited, &jumps) {
+            if changes.contains(&coord) {
+                continue;
+            }
+            let mut next = changes.clone();
+            next.push(coord);
+            next.sort();
+            if seen.insert(next.clone()) {
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// the fewest new obstructions (at most `max_added`) that trap the guard in a loop
+///
+/// empty when the guard already loops, `None` when `max_added` aren't enough
+pub fn fewest_to_trap(map_unvisited: &Map, max_added: usize) -> Option<Vec<(usize, usize)>> {
+    let start = (map_unvisited.guard.row, map_unvisited.guard.col);
+    fewest_changes(
+        map_unvisited,
+        max_added,
+        |outcome| matches!(outcome, Outcome::Loop { .. }),
+        // anywhere on the guard's new path, except where it starts
+        |map_visited, _| {
+            map_visited
+                .iter_visited()
+                .filter(|&uu| uu != start)
+                .collect()
+        },
+    )
+}
+
+/// the fewest obstructions to take away (at most `max_removed`) so that a trapped guard leaves
+/// the map
+///
+/// empty when the guard already leaves, `None` when `max_removed` aren't enough
+pub fn fewest_to_free(map_unvisited: &Map, max_removed: usize) -> Option<Vec<(usize, usize)>> {
+    fewest_changes(
+        map_unvisited,
+        max_removed,
+        |outcome| matches!(outcome, Outcome::Exited { .. }),
+        // the obstructions the guard turned at, in front of it before each turn
+        |map_visited, jumps| {
+            let mut turned_at: Vec<(usize, usize)> = jumps.turns[1..]
+                .iter()
+                .filter_map(|(_, g)| {
+                    g.orientation.turn_left().step(
+                        g.row,
+                        g.col,
+                        map_visited.rows(),
+                        map_visited.cols(),
+                    )
+                })
+                .collect();
+            turned_at.sort();
+            turned_at.dedup();
+            turned_at
+        },
+    )
+}
+
+/// distinct positions the guard visits before leaving `map`, `None` if it loops first or
+/// visits `cap` or more; the guard mustn't have patrolled `map` yet
+fn count_before_exit(map: &mut Map, cap: usize) -> Option<usize> {
+    // its start, and then each position that hadn't been visited yet
+    let mut count = 1;
+    while let Some(before) = map.step() {
+        if before & (1 << map.guard.orientation as usize) != 0 {
+            return None;
+        }
+        if before == 0 {
+            count += 1;
+            if count >= cap {
+                return None;
+            }
+        }
+    }
+    Some(count)
+}
+
+/// the single new obstruction on the guard's path that leaves it visiting the most (or fewest)
+/// distinct positions before it leaves the map, and that count; `None` if every one traps it
+///
+/// ties go to the first in row-major order. Looking for the fewest is branch and bound, a
+/// patrol is given up as soon as it visits as many positions as the best so far.
+pub fn extreme_obstruction(
+    map_unvisited: &Map,
+    extreme: Extreme,
+) -> Option<((usize, usize), usize)> {
+    let start = (map_unvisited.guard.row, map_unvisited.guard.col);
+    let (map_visited, _) = patrol(map_unvisited, &[]);
+
+    let mut best: Option<((usize, usize), usize)> = None;
+    for coord in map_visited.iter_visited().filter(|&uu| uu != start) {
+        let cap = match (extreme, best) {
+            (Extreme::Fewest, Some((_, 1))) => break,
+            (Extreme::Fewest, Some((_, fewest))) => fewest,
+            _ => usize::MAX,
+        };
+        let mut map = map_unvisited.clone();
+        map.set_obstruction(coord, true);
+        let Some(count) = count_before_exit(&mut map, cap) else {
+            continue;
+        };
+        let better = match (extreme, best) {
+            (_, None) => true,
+            (Extreme::Most, Some((_, most))) => count > most,
+            (Extreme::Fewest, Some((_, fewest))) => count < fewest,
+        };
+        if better {
+            best = Some((coord, count));
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::optimise::*;
+    use aoc_common::readme;
+    use std::str::FromStr;
+
+    fn example() -> Map {
+        let input_txt = readme::example(include_str!("../README.md"), "For example:").unwrap();
+        Map::from_str(&input_txt).unwrap()
+    }
+
+    #[test]
+    fn test_fewest_to_trap() {
+        // one is enough, the first of the puzzle's six
+        let map_unvisited = example();
+        assert_eq!(Some(vec![(6, 3)]), fewest_to_trap(&map_unvisited, 2));
+
+        // on an empty floor the guard needs boxing in on all four sides
+        let map_unvisited = Map::from_str("....\n....\n.^..\n....\n").unwrap();
+        assert_eq!(None, fewest_to_trap(&map_unvisited, 3));
+        let added = fewest_to_trap(&map_unvisited, 4).unwrap();
+        assert_eq!(4, added.len());
+        let (_, jumps) = patrol(&map_unvisited, &added);
+        assert!(matches!(jumps.outcome, Outcome::Loop { .. }), "{added:?}");
+
+        // already stuck
+        let boxed = Map::from_str(".#..\n.^.#\n#...\n..#.\n").unwrap();
+        assert_eq!(Some(vec![]), fewest_to_trap(&boxed, 1));
+    }
+
+    #[test]
+    fn test_fewest_to_free() {
+        let boxed = Map::from_str(".#..\n.^.#\n#...\n..#.\n").unwrap();
+        let removed = fewest_to_free(&boxed, 2).unwrap();
+        assert_eq!(vec![(0, 1)], removed);
+        assert_eq!(Some(vec![]), fewest_to_free(&example(), 2));
+
+        // option five from the puzzle, undone by taking one obstruction away
+        let mut trapped = example();
+        trapped.set_obstruction((8, 3), true);
+        let (_, jumps) = patrol(&trapped, &[]);
+        assert!(matches!(jumps.outcome, Outcome::Loop { .. }));
+        let removed = fewest_to_free(&trapped, 2).unwrap();
+        assert_eq!(1, removed.len());
+        let (_, jumps) = patrol(&trapped, &removed);
+        assert!(
+            matches!(jumps.outcome, Outcome::Exited { .. }),
+            "{removed:?}"
+        );
+    }
+
+    #[test]
+    fn test_extreme_obstruction() {
+        let map_unvisited = example();
+
+        // the same as trying every position on the path in full
+        let mut counts = Vec::new();
+        let (map_visited, _) = patrol(&map_unvisited, &[]);
+        for coord in map_visited.iter_visited().filter(|&uu| uu != (6, 4)) {
+            let (m, jumps) = patrol(&map_unvisited, &[coord]);
+            if let Outcome::Exited { .. } = jumps.outcome {
+                counts.push((coord, m.count_positions_visited()));
+            }
+        }
+        let most = counts.iter().map(|(_, count)| *count).max().unwrap();
+        let fewest = counts.iter().map(|(_, count)| *count).min().unwrap();
+        let first_with = |n| counts.iter().find(|(_, count)| *count == n).copied();
+
+        assert_eq!(
+            first_with(most),
+            extreme_obstruction(&map_unvisited, Extreme::Most)
+        );
+        assert_eq!(
+            first_with(fewest),
+            extreme_obstruction(&map_unvisited, Extreme::Fewest)
+        );
+        // right in front of the guard it turns and walks straight off, and nothing on its path
+        // makes the patrol longer than without a new obstruction
+        assert_eq!((6, 40), (fewest, most));
+        assert_eq!(Some(((5, 4), 6)), first_with(fewest));
+
+        // right in front of the guard it visits only its start, and no patrol can beat that
+        let map_unvisited = Map::from_str("v\n.\n.\n").unwrap();
+        assert_eq!(
+            Some(((1, 0), 1)),
+            extreme_obstruction(&map_unvisited, Extreme::Fewest)
+        );
+        assert_eq!(
+            Some(((2, 0), 2)),
+            extreme_obstruction(&map_unvisited, Extreme::Most)
+        );
+    }
+}
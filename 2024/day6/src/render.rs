@@ -0,0 +1,151 @@
+//! Drawing a map as text: the guard's route, which ways it faced at each position, and where
+//! new obstructions go
+
+use crate::{Guard, Map, Orientation};
+use std::fmt::Write;
+
+/// moved up or down through a position
+const VERTICAL: u8 = 1;
+/// moved left or right through a position
+const HORIZONTAL: u8 = 2;
+
+fn axis(orientation: Orientation) -> u8 {
+    match orientation {
+        Orientation::Up | Orientation::Down => VERTICAL,
+        Orientation::Right | Orientation::Left => HORIZONTAL,
+    }
+}
+
+/// ANSI background colours for 1, 2, 3 and 4 orientations
+const HEAT: [u8; 4] = [44, 46, 42, 41];
+
+impl Map {
+    /// the guard's route from its start, in the puzzle's notation, with `O` at each of `overlay`
+    ///
+    /// `|` is a position the guard moves up/down through, `-` left/right, and `+` both (or a
+    /// turn); the guard is drawn where it starts. A looping guard is drawn once around its loop.
+    pub fn render_path(&self, overlay: &[(usize, usize)]) -> String {
+        let (rows, cols) = (self.rows(), self.cols());
+        let mut axes = vec![0u8; rows * cols];
+
+        let mut walk = self.clone();
+        let mut before: Guard = walk.guard.clone();
+        // after `rows * cols * 4` steps every state has been reached, a loop has come all the way round
+        for step in (&mut walk).take(rows * cols * 4) {
+            if step.orientation == before.orientation {
+                axes[before.row * cols + before.col] |= axis(step.orientation);
+                axes[step.row * cols + step.col] |= axis(step.orientation);
+            } else {
+                axes[step.row * cols + step.col] |= VERTICAL | HORIZONTAL;
+            }
+            before = step;
+        }
+
+        let mut out = String::new();
+        for rr in 0..rows {
+            for cc in 0..cols {
+                let glyph = if (rr, cc) == (self.guard.row, self.guard.col) {
+                    self.guard.glyph()
+                } else if overlay.contains(&(rr, cc)) {
+                    'O'
+                } else if self.is_obstruction((rr, cc)) {
+                    '#'
+                } else {
+                    match axes[rr * cols + cc] {
+                        VERTICAL => '|',
+                        HORIZONTAL => '-',
+                        0 => '.',
+                        _ => '+',
+                    }
+                };
+                out.push(glyph);
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// how many ways the guard has faced at each position, as an ANSI-coloured heatmap, for a
+    /// map the guard has already walked
+    ///
+    /// each visited position shows its count on a background from blue (one way) to red (all 4)
+    pub fn render_heatmap(&self) -> String {
+        let mut out = String::new();
+        for rr in 0..self.rows() {
+            for cc in 0..self.cols() {
+                let count = self.mask((rr, cc)).count_ones();
+                if (rr, cc) == (self.guard.row, self.guard.col) {
+                    out.push(self.guard.glyph());
+                } else if self.is_obstruction((rr, cc)) {
+                    out.push('#');
+                } else if count == 0 {
+                    out.push('.');
+                } else {
+                    let heat = HEAT[count as usize - 1];
+                    write!(out, "\x1b[30;{heat}m{count}\x1b[0m").unwrap();
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::render::*;
+    use crate::{Search, loop_obstructions};
+    use aoc_common::readme;
+    use std::str::FromStr;
+
+    const README: &str = include_str!("../README.md");
+
+    #[test]
+    fn test_render_path() {
+        let map_unvisited =
+            Map::from_str(&readme::example(README, "For example:").unwrap()).unwrap();
+
+        // the six options drawn in the puzzle
+        let options = [
+            ("starting position:", (6, 3)),
+            ("quadrant of the mapped area:", (7, 6)),
+            ("standing desk in the bottom right quadrant:", (7, 7)),
+            ("near the bottom left corner:", (8, 1)),
+            ("a bit to the right instead:", (8, 3)),
+            ("universal solvent:", (9, 7)),
+        ];
+        for (intro, coord) in options {
+            let mut m = map_unvisited.clone();
+            m.set_obstruction(coord, true);
+            assert_eq!(
+                readme::example(README, intro).unwrap(),
+                m.render_path(&[coord]),
+                "{intro}"
+            );
+        }
+
+        // every option on the route without any of them
+        let loops = loop_obstructions(&map_unvisited, 1, Search::FromPrefix);
+        let overlay = map_unvisited.render_path(&loops);
+        assert_eq!(6, overlay.matches('O').count());
+        assert_eq!(Some("#O-O--+|.."), overlay.lines().nth(8));
+    }
+
+    #[test]
+    fn test_render_heatmap() {
+        let mut m = Map::from_str(".#...\n....#\n.....\n#....\n.^.#.\n").unwrap();
+        for _step in (&mut m).take(30) {}
+
+        // once round the loop and most of the way round again: going round again doesn't add
+        // to the count, and the corners are faced two ways
+        let heatmap = m.render_heatmap();
+        let plain: String = heatmap
+            .split('\x1b')
+            .map(|s| s.split_once('m').map_or(s, |(_, rest)| rest))
+            .collect();
+        assert_eq!(".#...\n.21>#\n.1.1.\n#212.\n.1.#.\n", plain);
+        assert!(heatmap.contains("\x1b[30;44m1\x1b[0m"), "{heatmap:?}");
+        assert!(heatmap.contains("\x1b[30;46m2\x1b[0m"), "{heatmap:?}");
+    }
+}
@@ -0,0 +1,464 @@
+//! Other ways for the guard to patrol, and other kinds of tiles for it to run into
+//!
+//! the puzzle's guard turns right at every obstruction, that is [`Rules::default`]. Everything
+//! here is simulated a step at a time, so it is slower than [`Map::patrol`] and the
+//! [`JumpTable`](crate::jump::JumpTable).
+
+use crate::{Guard, Map, Orientation, Outcome, parallel};
+use std::collections::{BTreeMap, HashMap};
+
+/// which way the guard turns when something blocks it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Turn {
+    /// the puzzle's rule
+    #[default]
+    Right,
+    Left,
+    Around,
+    /// right, then left, then right, ...
+    Alternate,
+    /// right, left or around, picked by an 8-bit LFSR started from `seed` (0 is taken as 1)
+    ///
+    /// the LFSR state is part of the guard's state, with only 255 of them a trapped guard
+    /// still repeats itself soon enough to be caught
+    Random {
+        seed: u8,
+    },
+}
+
+/// what the edge of the map does to the guard
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Edge {
+    /// the puzzle's rule, the guard walks off the map and the patrol ends
+    #[default]
+    Exit,
+    /// opposite edges are joined, the guard walks off one and back on at the other
+    Wrap,
+    /// the edge blocks the guard like an obstruction
+    Wall,
+}
+
+/// a position with more to it than floor or obstruction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tile {
+    /// floor that can only be walked onto facing this way, it blocks the guard otherwise
+    OneWay(Orientation),
+    /// an obstruction that crumbles after it has blocked the guard `hits` times
+    Breakable { hits: u32 },
+}
+
+/// How the guard patrols
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Rules {
+    pub turn: Turn,
+    /// with [`Edge::Wrap`] or [`Edge::Wall`] the guard never leaves, every patrol ends in a loop
+    pub edge: Edge,
+    /// special tiles, an obstruction on the map takes precedence over a tile
+    pub tiles: BTreeMap<(usize, usize), Tile>,
+}
+
+/// next state of the 8-bit Galois LFSR with taps 8, 6, 5, 4, which goes through all 255
+/// non-zero states
+fn lfsr(state: u8) -> u8 {
+    let shifted = state >> 1;
+    if state & 1 == 1 {
+        shifted ^ 0xb8
+    } else {
+        shifted
+    }
+}
+
+/// The guard, and everything else that changes as it patrols under some rules
+#[derive(Clone, Debug)]
+struct Patroller<'a> {
+    map: &'a Map,
+    rules: &'a Rules,
+    guard: Guard,
+    /// which way [`Turn::Alternate`] turns next, or the LFSR of [`Turn::Random`]
+    turn_state: u8,
+    /// times each breakable tile has blocked the guard
+    hits: HashMap<(usize, usize), u32>,
+}
+
+impl<'a> Patroller<'a> {
+    fn new(map: &'a Map, rules: &'a Rules) -> Self {
+        Self {
+            map,
+            rules,
+            guard: map.guard.clone(),
+            turn_state: match rules.turn {
+                Turn::Random { seed } => seed.max(1),
+                _ => 0,
+            },
+            hits: HashMap::new(),
+        }
+    }
+
+    /// the guard's state for loop detection, the hits are left out as they only ever go up
+    fn key(&self) -> (usize, usize, Orientation, u8) {
+        (
+            self.guard.row,
+            self.guard.col,
+            self.guard.orientation,
+            self.turn_state,
+        )
+    }
+
+    fn turn(&mut self) -> Orientation {
+        let o = self.guard.orientation;
+        match self.rules.turn {
+            Turn::Right => o.turn_right(),
+            Turn::Left => o.turn_left(),
+            Turn::Around => o.reverse(),
+            Turn::Alternate => {
+                self.turn_state ^= 1;
+                if self.turn_state == 1 {
+                    o.turn_right()
+                } else {
+                    o.turn_left()
+                }
+            }
+            Turn::Random { .. } => {
+                self.turn_state = lfsr(self.turn_state);
+                match self.turn_state % 3 {
+                    0 => o.turn_right(),
+                    1 => o.turn_left(),
+                    _ => o.reverse(),
+                }
+            }
+        }
+    }
+
+    /// step or turn, `None` once the guard walks off the map
+    ///
+    /// returns whether a tile changed
+    fn step(&mut self) -> Option<bool> {
+        let (o, row, col) = (self.guard.orientation, self.guard.row, self.guard.col);
+        let (rows, cols) = (self.map.rows(), self.map.cols());
+        let (r, c) = match self.rules.edge {
+            Edge::Exit => o.step(row, col, rows, cols)?,
+            Edge::Wrap => o.step_wrapping(row, col, rows, cols),
+            Edge::Wall => match o.step(row, col, rows, cols) {
+                Some(coord) => coord,
+                None => {
+                    self.guard.orientation = self.turn();
+                    return Some(false);
+                }
+            },
+        };
+
+        let mut changed = false;
+        let blocked = self.map.is_obstruction((r, c))
+            || match self.rules.tiles.get(&(r, c)) {
+                None => false,
+                Some(Tile::OneWay(o)) => *o != self.guard.orientation,
+                Some(Tile::Breakable { hits }) => {
+                    let hit = self.hits.entry((r, c)).or_insert(0);
+                    changed = *hit < *hits;
+                    if changed {
+                        *hit += 1;
+                    }
+                    changed
+                }
+            };
+
+        if blocked {
+            self.guard.orientation = self.turn();
+        } else {
+            self.guard.row = r;
+            self.guard.col = c;
+        }
+        Some(changed)
+    }
+}
+
+/// the guard's state before its first step and after each one under `rules`, until it walks off
+/// the map, repeats itself, or `budget` steps run out, and how that patrol ended
+pub(crate) fn states_with(
+    map: &Map,
+    rules: &Rules,
+    budget: Option<usize>,
+) -> (Outcome, Vec<Guard>) {
+    let mut patroller = Patroller::new(map, rules);
+    let mut states = vec![map.guard.clone()];
+    // step at which the guard was first in each state, since a tile last changed
+    let mut seen = HashMap::new();
+    seen.insert(patroller.key(), 0);
+
+    let mut steps = 0;
+    let outcome = loop {
+        if budget.is_some_and(|b| steps >= b) {
+            break Outcome::BudgetExhausted { steps };
+        }
+        let Some(changed) = patroller.step() else {
+            break Outcome::Exited { steps };
+        };
+        steps += 1;
+        states.push(patroller.guard.clone());
+
+        // the tiles can't go back to how they were, so nothing before now can repeat
+        if changed {
+            seen.clear();
+        }
+        if let Some(start) = seen.insert(patroller.key(), steps) {
+            break Outcome::Loop {
+                start,
+                length: steps - start,
+            };
+        }
+    };
+    (outcome, states)
+}
+
+impl Map {
+    /// [`Map::patrol`] under other `rules`, with the same outcome and visits for the default rules
+    pub fn patrol_with(&mut self, rules: &Rules, budget: Option<usize>) -> Outcome {
+        let (outcome, states) = states_with(self, rules, budget);
+        for state in &states {
+            self.mark(state);
+        }
+        self.guard = states.last().unwrap().clone();
+        outcome
+    }
+}
+
+/// [`loop_obstructions`](crate::loop_obstructions) under other `rules`, from the start for
+/// every candidate
+pub fn loop_obstructions_with(
+    map_unvisited: &Map,
+    rules: &Rules,
+    threads: usize,
+) -> Vec<(usize, usize)> {
+    let mut map_visited = map_unvisited.clone();
+    map_visited.patrol_with(rules, None);
+
+    // NOTE: cannot put obstruction at the Guard's starting point!!!
+    let start = (map_unvisited.guard.row, map_unvisited.guard.col);
+    let candidates: Vec<(usize, usize)> = map_visited
+        .iter_visited()
+        .filter(|&uu| uu != start)
+        .collect();
+
+    parallel::filter_map(&candidates, threads, |&coord| {
+        let mut blocked = map_unvisited.clone();
+        blocked.set_obstruction(coord, true);
+        match blocked.patrol_with(rules, None) {
+            Outcome::Loop { .. } => Some(coord),
+            _ => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::rules::*;
+    use crate::{Search, loop_obstructions};
+    use aoc_common::readme;
+    use std::str::FromStr;
+
+    fn example() -> Map {
+        let input_txt = readme::example(include_str!("../README.md"), "For example:").unwrap();
+        Map::from_str(&input_txt).unwrap()
+    }
+
+    #[test]
+    fn test_default_rules() {
+        let map_unvisited = example();
+        let rules = Rules::default();
+
+        // same outcome and visits as the puzzle's patrol, for every obstruction
+        for coord in map_unvisited.coords() {
+            let mut single = map_unvisited.clone();
+            single.set_obstruction(coord, coord != (6, 4));
+            for budget in [None, Some(0), Some(6), Some(40)] {
+                let mut expected = single.clone();
+                let mut m = single.clone();
+                assert_eq!(
+                    expected.patrol(budget),
+                    m.patrol_with(&rules, budget),
+                    "{coord:?} {budget:?}"
+                );
+                assert_eq!(expected.guard, m.guard, "{coord:?} {budget:?}");
+                assert_eq!(expected.directions, m.directions, "{coord:?} {budget:?}");
+            }
+        }
+
+        for threads in [1, 4] {
+            assert_eq!(
+                loop_obstructions(&map_unvisited, 1, Search::FromStart),
+                loop_obstructions_with(&map_unvisited, &rules, threads)
+            );
+        }
+    }
+
+    #[test]
+    fn test_turns() {
+        let map_unvisited = example();
+
+        // turning left on the mirror image is the same patrol
+        let input_txt = readme::example(include_str!("../README.md"), "For example:").unwrap();
+        let mirrored_txt: String = input_txt
+            .lines()
+            .map(|l| l.chars().rev().collect::<String>() + "\n")
+            .collect();
+        let mirrored = Map::from_str(&mirrored_txt).unwrap();
+        let left = Rules {
+            turn: Turn::Left,
+            ..Rules::default()
+        };
+        assert_eq!(
+            map_unvisited.clone().patrol(None),
+            mirrored.clone().patrol_with(&left, None)
+        );
+        let mirror = |coords: Vec<(usize, usize)>| {
+            let mut coords: Vec<_> = coords.into_iter().map(|(r, c)| (r, 9 - c)).collect();
+            coords.sort();
+            coords
+        };
+        assert_eq!(
+            loop_obstructions(&map_unvisited, 1, Search::FromStart),
+            mirror(loop_obstructions_with(&mirrored, &left, 1))
+        );
+
+        // turning around below (0, 4): 5 up, a turn, then 8 down and off the map
+        let around = Rules {
+            turn: Turn::Around,
+            ..Rules::default()
+        };
+        let mut m = map_unvisited.clone();
+        assert_eq!(Outcome::Exited { steps: 14 }, m.patrol_with(&around, None));
+        assert_eq!(9, m.count_positions_visited());
+
+        // boxed in: right, left, right, left never gets anywhere but doesn't repeat until the
+        // alternation does
+        let boxed = Map::from_str(".#.\n#^#\n.#.\n").unwrap();
+        let alternate = Rules {
+            turn: Turn::Alternate,
+            ..Rules::default()
+        };
+        assert_eq!(
+            Outcome::Loop {
+                start: 0,
+                length: 2
+            },
+            boxed.clone().patrol_with(&alternate, None)
+        );
+
+        // random turns: the same seed gives the same patrol, and a trapped guard is still caught
+        for seed in [0, 1, 7, 200] {
+            let random = Rules {
+                turn: Turn::Random { seed },
+                ..Rules::default()
+            };
+            let outcome = map_unvisited.clone().patrol_with(&random, None);
+            assert_eq!(outcome, map_unvisited.clone().patrol_with(&random, None));
+            assert!(matches!(
+                boxed.clone().patrol_with(&random, None),
+                Outcome::Loop { .. }
+            ));
+            let loops = loop_obstructions_with(&map_unvisited, &random, 2);
+            assert_eq!(loops, loop_obstructions_with(&map_unvisited, &random, 1));
+        }
+    }
+
+    #[test]
+    fn test_tiles() {
+        let boxed = Map::from_str(".#.\n#^#\n.#.\n").unwrap();
+
+        // the top breaks on the third time round, and the guard walks out
+        let mut rules = Rules::default();
+        rules.tiles.insert((0, 1), Tile::Breakable { hits: 2 });
+        let mut m = boxed.clone();
+        m.set_obstruction((0, 1), false);
+        assert_eq!(Outcome::Exited { steps: 9 }, m.patrol_with(&rules, None));
+        assert!(m.is_visited((0, 1)));
+
+        // a one-way tile that can only be walked onto going down, so the guard turns there
+        let map_unvisited = example();
+        let mut rules = Rules::default();
+        rules.tiles.insert((3, 4), Tile::OneWay(Orientation::Down));
+        let mut m = map_unvisited.clone();
+        let outcome = m.patrol_with(&rules, None);
+        assert!(!m.is_visited((3, 4)));
+        assert!(m.is_visited((4, 4)));
+        assert_ne!(map_unvisited.clone().patrol(None), outcome);
+
+        // going down it is floor
+        let mut rules = Rules::default();
+        rules.tiles.insert((3, 8), Tile::OneWay(Orientation::Down));
+        assert_eq!(
+            map_unvisited.clone().patrol(None),
+            map_unvisited.clone().patrol_with(&rules, None)
+        );
+
+        // an obstruction to break on every candidate, the search still finishes
+        let mut rules = Rules::default();
+        rules.tiles.insert((0, 4), Tile::Breakable { hits: 1 });
+        let loops = loop_obstructions_with(&map_unvisited, &rules, 2);
+        assert_eq!(loops, loop_obstructions_with(&map_unvisited, &rules, 1));
+    }
+
+    #[test]
+    fn test_edges() {
+        let input_txt = readme::example(include_str!("../README.md"), "For example:").unwrap();
+        let map_unvisited = example();
+
+        // a wall round the map is the same as a ring of obstructions
+        let ring = "#".repeat(12) + "\n";
+        let padded_txt: String = ring.clone()
+            + &input_txt
+                .lines()
+                .map(|l| format!("#{l}#\n"))
+                .collect::<String>()
+            + &ring;
+        let padded = Map::from_str(&padded_txt).unwrap();
+        let wall = Rules {
+            edge: Edge::Wall,
+            ..Rules::default()
+        };
+        let mut m = map_unvisited.clone();
+        let outcome = m.patrol_with(&wall, None);
+        assert!(matches!(outcome, Outcome::Loop { .. }), "{outcome:?}");
+        let mut p = padded.clone();
+        assert_eq!(p.patrol(None), outcome);
+        for (r, c) in m.coords() {
+            assert_eq!(p.mask((r + 1, c + 1)), m.mask((r, c)), "{:?}", (r, c));
+        }
+        let unpad = |coords: Vec<(usize, usize)>| -> Vec<(usize, usize)> {
+            coords.into_iter().map(|(r, c)| (r - 1, c - 1)).collect()
+        };
+        assert_eq!(
+            unpad(loop_obstructions_with(&padded, &Rules::default(), 1)),
+            loop_obstructions_with(&map_unvisited, &wall, 2)
+        );
+
+        // on a torus an empty map is a loop round and round one column
+        let wrap = Rules {
+            edge: Edge::Wrap,
+            ..Rules::default()
+        };
+        let mut m = Map::from_str("...\n.^.\n...\n").unwrap();
+        assert_eq!(
+            Outcome::Loop {
+                start: 0,
+                length: 3
+            },
+            m.patrol_with(&wrap, None)
+        );
+        assert_eq!(
+            vec![(0, 1), (1, 1), (2, 1)],
+            m.iter_visited().collect::<Vec<_>>()
+        );
+
+        // the guard can't get away, so any new obstruction leaves it in a loop
+        let mut m = map_unvisited.clone();
+        let outcome = m.patrol_with(&wrap, None);
+        assert!(matches!(outcome, Outcome::Loop { .. }), "{outcome:?}");
+        let candidates = m.iter_visited().filter(|&c| c != (6, 4)).count();
+        assert_eq!(
+            candidates,
+            loop_obstructions_with(&map_unvisited, &wrap, 2).len()
+        );
+    }
+}
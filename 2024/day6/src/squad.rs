@@ -0,0 +1,404 @@
+//! Several guards patrolling the same map at once, one step each per tick
+//!
+//! guards don't block each other, they walk through one another; where two of them meet is
+//! reported as a [`Collision`]
+
+use crate::{
+    Guard, Map, Outcome,
+    jump::{JumpTable, Seen},
+    parse_map,
+};
+use anyhow::Result;
+use aoc_common::parallel;
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// how two guards met
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Meeting {
+    /// both guards ended the step on `coord`
+    Same { coord: (usize, usize) },
+    /// the first guard walked `from` → `to` while the second walked `to` → `from`
+    Swap {
+        from: (usize, usize),
+        to: (usize, usize),
+    },
+}
+
+/// two guards meeting at a step, `guards` are indices in parse order (first one lower)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Collision {
+    pub step: usize,
+    pub guards: (usize, usize),
+    pub meeting: Meeting,
+}
+
+/// how a [`Squad::patrol`] went
+#[derive(Clone, Debug, PartialEq)]
+pub struct SquadPatrol {
+    /// each guard's outcome, the same as it would be patrolling alone
+    pub outcomes: Vec<Outcome>,
+    pub collisions: Vec<Collision>,
+    /// ticks until every guard's outcome was known
+    pub steps: usize,
+}
+
+/// which guards a new obstruction has to trap, for [`Squad::loop_obstructions`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Trap {
+    /// at least one guard loops
+    Any,
+    /// every guard loops
+    All,
+}
+
+/// A map with any number of guards on it
+#[derive(Clone, Debug)]
+pub struct Squad {
+    /// obstructions and the combined visits of every guard, its own guard is the first one
+    map: Map,
+    /// in row-major order of where they start, which is also the order they step in
+    guards: Vec<Guard>,
+    /// positions each guard has walked into
+    walked: Vec<Vec<bool>>,
+}
+
+impl FromStr for Squad {
+    type Err = anyhow::Error;
+
+    /// like [`Map::from_str`], with any number of guards (at least one)
+    fn from_str(input_txt: &str) -> Result<Self> {
+        let (map, guards) = parse_map(input_txt)?;
+        let cols = map.cols();
+        let walked = guards
+            .iter()
+            .map(|g| {
+                let mut w = vec![false; map.rows() * cols];
+                w[g.row * cols + g.col] = true;
+                w
+            })
+            .collect();
+        Ok(Squad {
+            map,
+            guards,
+            walked,
+        })
+    }
+}
+
+impl Display for Squad {
+    /// the map as it was parsed, with every guard where it is now
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for rr in 0..self.map.rows() {
+            for cc in 0..self.map.cols() {
+                let glyph = match self.guards.iter().find(|g| (g.row, g.col) == (rr, cc)) {
+                    Some(guard) => guard.glyph(),
+                    None if self.map.is_obstruction((rr, cc)) => '#',
+                    None => '.',
+                };
+                write!(f, "{glyph}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Squad {
+    pub fn guards(&self) -> &[Guard] {
+        &self.guards
+    }
+
+    /// distinct positions visited by any guard
+    pub fn count_positions_visited(&self) -> usize {
+        self.map.count_positions_visited()
+    }
+
+    /// distinct positions visited by the guard at `idx`
+    pub fn coverage(&self, idx: usize) -> usize {
+        self.walked[idx].iter().filter(|&&w| w).count()
+    }
+
+    /// walk every guard a step per tick, in order, until each has left the map or is known to
+    /// loop, or `budget` ticks run out
+    ///
+    /// a guard's visits stop counting once its outcome is known, but a looping guard keeps
+    /// walking so that the others can still meet it
+    pub fn patrol(&mut self, budget: Option<usize>) -> SquadPatrol {
+        const UNSEEN: u32 = u32::MAX;
+
+        let (rows, cols) = (self.map.rows(), self.map.cols());
+        let state = |g: &Guard| (g.row * cols + g.col) * 4 + g.orientation as usize;
+        // step at which each guard was first in each (cell, orientation), as in Map::patrol
+        let mut seen: Vec<Vec<u32>> = self
+            .guards
+            .iter()
+            .map(|g| {
+                let mut s = vec![UNSEEN; rows * cols * 4];
+                s[state(g)] = 0;
+                s
+            })
+            .collect();
+        let mut outcomes: Vec<Option<Outcome>> = vec![None; self.guards.len()];
+        let mut on_map = vec![true; self.guards.len()];
+        let mut collisions = Vec::new();
+
+        let mut steps = 0;
+        while outcomes.iter().any(Option::is_none) {
+            if budget.is_some_and(|b| steps >= b) {
+                for outcome in outcomes.iter_mut().filter(|o| o.is_none()) {
+                    *outcome = Some(Outcome::BudgetExhausted { steps });
+                }
+                break;
+            }
+            steps += 1;
+
+            let before: Vec<(usize, usize)> = self.guards.iter().map(|g| (g.row, g.col)).collect();
+            for (idx, guard) in self.guards.iter_mut().enumerate() {
+                if !on_map[idx] {
+                    continue;
+                }
+                let Some((r, c)) = guard.orientation.step(guard.row, guard.col, rows, cols) else {
+                    // a guard that's known to loop never gets here
+                    on_map[idx] = false;
+                    outcomes[idx] = Some(Outcome::Exited { steps: steps - 1 });
+                    continue;
+                };
+
+                let counting = outcomes[idx].is_none();
+                if self.map.is_obstruction((r, c)) {
+                    guard.orientation = guard.orientation.turn_right();
+                } else {
+                    guard.row = r;
+                    guard.col = c;
+                }
+
+                if counting {
+                    self.map.mark(guard);
+                    self.walked[idx][guard.row * cols + guard.col] = true;
+                    let first = &mut seen[idx][state(guard)];
+                    if *first != UNSEEN {
+                        let start = *first as usize;
+                        outcomes[idx] = Some(Outcome::Loop {
+                            start,
+                            length: steps - start,
+                        });
+                    } else {
+                        *first = steps as u32;
+                    }
+                }
+            }
+
+            for ii in 0..self.guards.len() {
+                for jj in ii + 1..self.guards.len() {
+                    if !(on_map[ii] && on_map[jj]) {
+                        continue;
+                    }
+                    let here = |g: &Guard| (g.row, g.col);
+                    let (a, b) = (here(&self.guards[ii]), here(&self.guards[jj]));
+                    let meeting = if a == b {
+                        Meeting::Same { coord: a }
+                    } else if (before[ii], before[jj]) == (b, a) {
+                        Meeting::Swap { from: b, to: a }
+                    } else {
+                        continue;
+                    };
+                    collisions.push(Collision {
+                        step: steps,
+                        guards: (ii, jj),
+                        meeting,
+                    });
+                }
+            }
+        }
+
+        SquadPatrol {
+            outcomes: outcomes.into_iter().flatten().collect(),
+            collisions,
+            steps,
+        }
+    }
+
+    /// positions where a single new obstruction traps the guards `trap` asks for, in row-major
+    /// order, for a squad that hasn't patrolled yet
+    ///
+    /// guards don't affect each other, so each guard is checked alone, and only when the
+    /// position is on its own path; no guard's start can be obstructed
+    pub fn loop_obstructions(&self, threads: usize, trap: Trap) -> Vec<(usize, usize)> {
+        let mut squad_visited = self.clone();
+        let baseline: Vec<bool> = squad_visited
+            .patrol(None)
+            .outcomes
+            .iter()
+            .map(|o| matches!(o, Outcome::Loop { .. }))
+            .collect();
+        let cols = self.map.cols();
+
+        let candidates: Vec<(usize, usize)> = squad_visited
+            .map
+            .iter_visited()
+            .filter(|&uu| !self.guards.iter().any(|g| (g.row, g.col) == uu))
+            .collect();
+
+        let table = JumpTable::new(&self.map);
+        parallel::filter_map_with(
+            &candidates,
+            threads,
+            || Seen::new(&table),
+            |seen, &coord| {
+                let mut trapped = self.guards.iter().enumerate().map(|(idx, guard)| {
+                    if squad_visited.walked[idx][coord.0 * cols + coord.1] {
+                        table.loops(guard, Some(coord), seen)
+                    } else {
+                        baseline[idx]
+                    }
+                });
+                let hit = match trap {
+                    Trap::Any => trapped.any(|t| t),
+                    Trap::All => trapped.all(|t| t),
+                };
+
+                hit.then_some(coord)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::squad::*;
+    use crate::{Search, loop_obstructions};
+    use aoc_common::readme;
+
+    fn example_txt() -> String {
+        readme::example(include_str!("../README.md"), "For example:").unwrap()
+    }
+
+    #[test]
+    fn test_single_guard() {
+        let map_unvisited = Map::from_str(&example_txt()).unwrap();
+        let squad_unvisited = Squad::from_str(&example_txt()).unwrap();
+
+        let mut squad = squad_unvisited.clone();
+        let patrol = squad.patrol(None);
+        assert_eq!(vec![map_unvisited.clone().patrol(None)], patrol.outcomes);
+        assert_eq!(54 + 1, patrol.steps);
+        assert!(patrol.collisions.is_empty());
+        assert_eq!(
+            (41, 41),
+            (squad.count_positions_visited(), squad.coverage(0))
+        );
+
+        let loops = loop_obstructions(&map_unvisited, 1, Search::FromPrefix);
+        assert_eq!(loops, squad_unvisited.loop_obstructions(2, Trap::Any));
+        assert_eq!(loops, squad_unvisited.loop_obstructions(2, Trap::All));
+
+        let mut squad = squad_unvisited.clone();
+        let patrol = squad.patrol(Some(10));
+        assert_eq!(
+            vec![Outcome::BudgetExhausted { steps: 10 }],
+            patrol.outcomes
+        );
+    }
+
+    #[test]
+    fn test_collisions() {
+        // walking into the same position, then on through each other
+        let mut squad = Squad::from_str(">.<\n").unwrap();
+        let patrol = squad.patrol(None);
+        assert_eq!(
+            vec![Collision {
+                step: 1,
+                guards: (0, 1),
+                meeting: Meeting::Same { coord: (0, 1) }
+            }],
+            patrol.collisions
+        );
+        assert_eq!(vec![Outcome::Exited { steps: 2 }; 2], patrol.outcomes);
+        assert_eq!("<.>\n", squad.to_string());
+        assert_eq!(
+            (3, 3, 3),
+            (
+                squad.count_positions_visited(),
+                squad.coverage(0),
+                squad.coverage(1)
+            )
+        );
+
+        // swapping places without ever sharing one
+        let mut squad = Squad::from_str("><\n").unwrap();
+        let patrol = squad.patrol(None);
+        assert_eq!(
+            vec![Collision {
+                step: 1,
+                guards: (0, 1),
+                meeting: Meeting::Swap {
+                    from: (0, 0),
+                    to: (0, 1)
+                }
+            }],
+            patrol.collisions
+        );
+
+        // a turning guard stays put, and gets walked into
+        let mut squad = Squad::from_str(".#.\n.^<\n").unwrap();
+        let patrol = squad.patrol(None);
+        assert_eq!(
+            Meeting::Same { coord: (1, 1) },
+            patrol.collisions[0].meeting
+        );
+    }
+
+    #[test]
+    fn test_independent_guards() {
+        // one guard boxed in, two that meet on their way off the map
+        let squad_txt =
+            ".#........\n.^.#..v...\n#.........\n..#.......\n..........\n..........\n.>........\n";
+        let squad_unvisited = Squad::from_str(squad_txt).unwrap();
+        assert_eq!(3, squad_unvisited.guards().len());
+        assert!(Map::from_str(squad_txt).is_err());
+
+        let mut squad = squad_unvisited.clone();
+        let patrol = squad.patrol(None);
+        assert_eq!(
+            vec![Collision {
+                step: 5,
+                guards: (1, 2),
+                meeting: Meeting::Same { coord: (6, 6) }
+            }],
+            patrol.collisions
+        );
+
+        // each guard patrols as it would alone
+        for (idx, guard) in squad_unvisited.guards().iter().enumerate() {
+            let mut solo = squad_unvisited.map.clone();
+            solo.directions.fill(0);
+            solo.mark(guard);
+            solo.guard = guard.clone();
+            assert_eq!(solo.patrol(None), patrol.outcomes[idx], "{idx}");
+            assert_eq!(solo.count_positions_visited(), squad.coverage(idx), "{idx}");
+        }
+        assert_eq!(
+            Outcome::Loop {
+                start: 0,
+                length: 8
+            },
+            patrol.outcomes[0]
+        );
+        assert_eq!(
+            (4, 6, 9),
+            (squad.coverage(0), squad.coverage(1), squad.coverage(2))
+        );
+        assert_eq!(4 + 6 + 9 - 1, squad.count_positions_visited());
+
+        // the boxed in guard stays trapped unless its own box is changed, the others never are
+        let trap_any = squad_unvisited.loop_obstructions(3, Trap::Any);
+        assert_eq!((6 - 1) + (9 - 1) - 1, trap_any.len());
+        assert!(!trap_any.contains(&(1, 2)));
+        assert!(trap_any.contains(&(6, 6)));
+        assert!(squad_unvisited.loop_obstructions(3, Trap::All).is_empty());
+    }
+}
@@ -0,0 +1,295 @@
+//! A recorded patrol: only the turns are kept, every other step is walking straight on
+//!
+//! a trace answers where the guard was at any step, when it first walked into a position and
+//! when it left the map, without simulating again
+
+use crate::{
+    Guard, Map, Orientation, Outcome,
+    jump::{JumpTable, Jumps},
+};
+use anyhow::{Context, Result, anyhow};
+use serde::{Serialize, Serializer};
+use std::{
+    fmt::{self, Display, Write as _},
+    fs,
+    path::Path,
+};
+
+/// what happened at a step of a [`Trace`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Event {
+    /// the guard's state before it starts
+    Start,
+    /// the guard turned right on the spot
+    Turn,
+    /// the guard's last step on the map, it walks off from here
+    Exit,
+    /// the guard is back in a state it was in at the start of its loop
+    Loop,
+    /// the step budget ran out
+    Budget,
+}
+
+impl Display for Event {
+    /// same as in JSON
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Event::Start => "start",
+            Event::Turn => "turn",
+            Event::Exit => "exit",
+            Event::Loop => "loop",
+            Event::Budget => "budget",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// one line of an exported trace
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TraceEvent {
+    pub step: usize,
+    pub event: Event,
+    pub row: usize,
+    pub col: usize,
+    #[serde(serialize_with = "orientation_name")]
+    pub facing: Orientation,
+}
+
+fn orientation_name<S: Serializer>(o: &Orientation, s: S) -> Result<S::Ok, S::Error> {
+    s.collect_str(&format_args!("{o:?}"))
+}
+
+/// The guard's whole patrol of a map, as its turns
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trace {
+    rows: usize,
+    cols: usize,
+    jumps: Jumps,
+}
+
+impl Map {
+    /// record the guard's patrol, stopping after `budget` steps if there is one
+    pub fn trace(&self, budget: Option<usize>) -> Trace {
+        Trace {
+            rows: self.rows(),
+            cols: self.cols(),
+            jumps: JumpTable::new(self).patrol(&self.guard, budget),
+        }
+    }
+}
+
+impl Trace {
+    pub fn outcome(&self) -> Outcome {
+        self.jumps.outcome
+    }
+
+    /// the last step of the trace, see [`Jumps::end`]
+    pub fn end(&self) -> usize {
+        self.jumps.end()
+    }
+
+    /// the guard's state after `step` steps, `None` past the end of the trace
+    pub fn state_at(&self, step: usize) -> Option<Guard> {
+        (step <= self.end()).then(|| self.jumps.state_at(step))
+    }
+
+    /// the step that takes the guard off the map, one after its last step on it
+    pub fn exit_step(&self) -> Option<usize> {
+        match self.outcome() {
+            Outcome::Exited { steps } => Some(steps + 1),
+            _ => None,
+        }
+    }
+
+    /// the first step the guard is on `coord`, 0 for its start
+    pub fn first_visit(&self, (row, col): (usize, usize)) -> Option<usize> {
+        let turns = &self.jumps.turns;
+        let start = &turns[0].1;
+        if (row, col) == (start.row, start.col) {
+            return Some(0);
+        }
+        let end = self.end();
+        turns
+            .iter()
+            .enumerate()
+            .find_map(|(idx, (turned_at, guard))| {
+                let until = turns.get(idx + 1).map_or(end, |(s, _)| s - 1);
+                let moves = match guard.orientation {
+                    Orientation::Up if col == guard.col && row < guard.row => guard.row - row,
+                    Orientation::Down if col == guard.col && row > guard.row => row - guard.row,
+                    Orientation::Left if row == guard.row && col < guard.col => guard.col - col,
+                    Orientation::Right if row == guard.row && col > guard.col => col - guard.col,
+                    _ => return None,
+                };
+                (turned_at + moves <= until).then_some(turned_at + moves)
+            })
+    }
+
+    /// the start, each turn, and how the patrol ended
+    pub fn events(&self) -> Vec<TraceEvent> {
+        let event = |step: usize, event: Event, guard: &Guard| TraceEvent {
+            step,
+            event,
+            row: guard.row,
+            col: guard.col,
+            facing: guard.orientation,
+        };
+
+        let mut events: Vec<TraceEvent> = self
+            .jumps
+            .turns
+            .iter()
+            .enumerate()
+            .map(|(idx, (step, guard))| {
+                event(
+                    *step,
+                    if idx == 0 { Event::Start } else { Event::Turn },
+                    guard,
+                )
+            })
+            .collect();
+        let end = self.end();
+        let last = match self.outcome() {
+            Outcome::Exited { .. } => Event::Exit,
+            Outcome::Loop { .. } => Event::Loop,
+            Outcome::BudgetExhausted { .. } => Event::Budget,
+        };
+        // a loop that closes on a turn already has its turn event
+        if events.last().is_some_and(|e| e.step == end) {
+            events.last_mut().unwrap().event = last;
+        } else {
+            events.push(event(end, last, &self.jumps.state_at(end)));
+        }
+        events
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        #[derive(Serialize)]
+        struct Json {
+            rows: usize,
+            cols: usize,
+            outcome: Outcome,
+            events: Vec<TraceEvent>,
+        }
+        let json = Json {
+            rows: self.rows,
+            cols: self.cols,
+            outcome: self.outcome(),
+            events: self.events(),
+        };
+        Ok(serde_json::to_string_pretty(&json)?)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("step,event,row,col,facing\n");
+        for e in self.events() {
+            writeln!(
+                csv,
+                "{},{},{},{},{:?}",
+                e.step, e.event, e.row, e.col, e.facing
+            )
+            .unwrap();
+        }
+        csv
+    }
+
+    /// write the trace as JSON or CSV, picked by the extension of `path`
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let trace_txt = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => self.to_json()?,
+            Some("csv") => self.to_csv(),
+            _ => return Err(anyhow!("{} should end in .json or .csv", path.display())),
+        };
+        fs::write(path, trace_txt).with_context(|| format!("could not write {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::trace::*;
+    use aoc_common::readme;
+    use std::str::FromStr;
+
+    fn example() -> Map {
+        let input_txt = readme::example(include_str!("../README.md"), "For example:").unwrap();
+        Map::from_str(&input_txt).unwrap()
+    }
+
+    #[test]
+    fn test_queries() {
+        let map_unvisited = example();
+        let trace = map_unvisited.trace(None);
+
+        // same states as stepping through the map
+        let mut m = map_unvisited.clone();
+        let mut step = 0;
+        assert_eq!(Some(m.guard.clone()), trace.state_at(0));
+        for guard in &mut m {
+            step += 1;
+            assert_eq!(Some(guard), trace.state_at(step), "{step}");
+        }
+        assert_eq!(54, step);
+        assert_eq!(None, trace.state_at(55));
+        assert_eq!(Some(55), trace.exit_step());
+
+        // first visits agree with a patrol with a budget
+        for coord in map_unvisited.coords() {
+            let first = trace.first_visit(coord);
+            assert_eq!(first.is_some(), m.is_visited(coord), "{coord:?}");
+            if let Some(step) = first.filter(|&step| step > 0) {
+                let mut before = map_unvisited.clone();
+                before.patrol(Some(step - 1));
+                let mut after = map_unvisited.clone();
+                after.patrol(Some(step));
+                assert!(!before.is_visited(coord), "{coord:?}");
+                assert!(after.is_visited(coord), "{coord:?}");
+            }
+        }
+        assert_eq!(Some(1), trace.first_visit((5, 4)));
+        assert_eq!(Some(7), trace.first_visit((1, 5)));
+        // the start is visited before the first step
+        assert_eq!(Some(0), trace.first_visit((6, 4)));
+
+        // a loop doesn't exit
+        let mut blocked = map_unvisited.clone();
+        blocked.set_obstruction((6, 3), true);
+        let trace = blocked.trace(None);
+        assert_eq!(
+            Outcome::Loop {
+                start: 0,
+                length: 22
+            },
+            trace.outcome()
+        );
+        assert_eq!(None, trace.exit_step());
+        assert_eq!(trace.state_at(0), trace.state_at(22));
+    }
+
+    #[test]
+    fn test_export() {
+        let m = Map::from_str("#..\n^..\n").unwrap();
+        let trace = m.trace(None);
+        assert_eq!(
+            "step,event,row,col,facing\n0,start,1,0,Up\n1,turn,1,0,Right\n3,exit,1,2,Right\n",
+            trace.to_csv()
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&trace.to_json().unwrap()).unwrap();
+        assert_eq!(2, json["rows"]);
+        assert_eq!("exited", json["outcome"]["kind"]);
+        assert_eq!(3, json["outcome"]["steps"]);
+        assert_eq!(3, json["events"].as_array().unwrap().len());
+        assert_eq!("turn", json["events"][1]["event"]);
+        assert_eq!("Right", json["events"][1]["facing"]);
+
+        // a loop that closes on a turn ends with a loop event there
+        let m = Map::from_str(".#.\n#^#\n.#.\n").unwrap();
+        let events = m.trace(None).events();
+        assert_eq!(5, events.len());
+        assert_eq!((4, Event::Loop), (events[4].step, events[4].event));
+        let budget = m.trace(Some(2)).events();
+        assert_eq!((2, Event::Budget), (budget[2].step, budget[2].event));
+    }
+}
@@ -0,0 +1,234 @@
+#![doc = include_str!("../README.md")]
+
+use anyhow::Result;
+use aoc_common::{input::DefaultInput, parse, solution::Solution};
+use itertools::Itertools;
+use memoize::memoize;
+use std::{fmt::Debug, str::FromStr};
+
+/// where to find the puzzle input by default
+pub const INPUT: DefaultInput = aoc_common::default_input!();
+
+/// missing operators allowed in the equation
+#[derive(Clone, Copy, Debug, PartialEq, Hash, Eq)]
+enum Operator {
+    Add,
+    Multiply,
+    Concatenate,
+}
+
+#[inline(always)]
+#[memoize]
+fn memoized_mul(lhs: u64, rhs: u64) -> u64 {
+    lhs.checked_mul(rhs).unwrap()
+}
+
+#[inline]
+#[memoize]
+fn concat_u64(lhs: u64, rhs: u64) -> u64 {
+    format!("{lhs}{rhs}").parse::<u64>().unwrap()
+}
+
+/// Ugly memoization function to memoize the permutations with replacement; not lazy
+#[inline]
+#[memoize]
+fn memoize_permutations_with_replacment(
+    operator_set: Vec<Operator>,
+    oplen: usize,
+) -> Vec<Vec<Operator>> {
+    itertools::repeat_n(operator_set, oplen)
+        .multi_cartesian_product()
+        .collect()
+}
+
+impl Operator {
+    // FIXME: learn how to manually memoize this method
+    fn apply(&self, lhs: u64, rhs: u64) -> u64 {
+        match self {
+            Self::Add => lhs + rhs,
+            Self::Multiply => memoized_mul(lhs, rhs),
+            Self::Concatenate => concat_u64(lhs, rhs),
+        }
+    }
+}
+
+/// represent an equation with a simple LHS and list of operands on the RHS
+#[derive(Clone, Debug)]
+struct Equation {
+    lhs: u64,
+    rhs: Vec<u64>,
+}
+
+/// EquationSolver is a struct that can solve an equation
+#[derive(Clone, Debug)]
+struct EquationSolver {
+    eqn: Equation,
+    operands: Vec<u64>,
+}
+
+impl EquationSolver {
+    fn new(eqn: Equation) -> Self {
+        Self {
+            eqn: eqn.clone(),
+            operands: eqn.rhs.clone(),
+        }
+    }
+
+    /// helper_solve_rec is a recursive helper function that evalutes the equation with the given operators
+    /// FIXME: too much cloning ... could probably use a more functional programming recursive pattern
+    fn helper_solve_rec(&self, combo: Option<Vec<Operator>>) -> Option<u64> {
+        if self.operands.len() == 1 {
+            return Some(self.operands[0]);
+        }
+
+        // mutable local copies on the recursion stack
+        let mut my_self = self.clone();
+        let mut my_combos = combo.clone().unwrap();
+
+        let tail_operand = my_self.operands.pop().unwrap();
+        let tail_operator = my_combos.pop().unwrap();
+
+        // optimization: recursed evaluation already short-circuited, so we can short-circuit
+        let val = my_self.helper_solve_rec(Some(my_combos))?;
+
+        let result = tail_operator.apply(val, tail_operand);
+
+        // optimization: if the result is greater than the LHS, then we can short-circuit
+        if result > self.eqn.lhs {
+            return None;
+        }
+
+        // println!(
+        //     "    {} {:?} {} = {}",
+        //     val.unwrap(),
+        //     tail_operator,
+        //     tail_operand,
+        //     result
+        // );
+        Some(result)
+    }
+
+    /// finds the operators (if any) for the equation
+    fn solve(&self, operator_set: Vec<Operator>) -> Result<()> {
+        let oplen = self.eqn.rhs.len() - 1;
+        // dbg!(oplen);
+
+        // generate all possible permutations (with replacement!!!) of the operators
+        let ordered_combos = memoize_permutations_with_replacment(operator_set, oplen);
+
+        // println!(
+        //     "Looking for {:?} from {} combos",
+        //     &self.eqn,
+        //     ordered_combos.clone().count()
+        // );
+
+        for combo in ordered_combos {
+            // dbg!(&combo);
+
+            let eval = self.helper_solve_rec(Some(combo.clone()));
+
+            if eval.is_some() && self.eqn.lhs == eval.unwrap() {
+                // println!(
+                //     "Found a solution: {:?} for {:?}",
+                //     combo,
+                //     self.clone().eqn.rhs
+                // );
+                return Ok(());
+            }
+        }
+
+        Err(anyhow::anyhow!("No solution found"))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Equations(Vec<Equation>);
+
+impl FromStr for Equations {
+    type Err = anyhow::Error;
+
+    /// parsing (came from CoPilot)
+    fn from_str(input_txt: &str) -> Result<Self> {
+        let mut eqns = Vec::<Equation>::new();
+        for line in parse::lines(input_txt) {
+            let (lhs, rhs) = line.split_once(": ")?;
+            let eqn = Equation {
+                lhs: line.field(lhs, "a test value")?,
+                rhs: rhs
+                    .split_ascii_whitespace()
+                    .map(|token| line.field(token, "an operand"))
+                    .collect::<Result<_, _>>()?,
+            };
+            eqns.push(eqn);
+        }
+
+        Ok(Self(eqns))
+    }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Equations;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input_txt: &str) -> Result<Self::Input> {
+        Equations::from_str(input_txt)
+    }
+
+    /// total calibration result using add and multiply
+    fn part1(eqns: &Self::Input) -> Result<u64> {
+        let mut part1_total_calibration_result = 0;
+        for eqn in eqns.0.iter() {
+            let solver = EquationSolver::new(eqn.clone());
+            // dbg!(&solver);
+
+            let solve = solver.solve(vec![Operator::Add, Operator::Multiply]);
+            if solve.is_ok() {
+                part1_total_calibration_result += eqn.lhs;
+            }
+        }
+
+        Ok(part1_total_calibration_result)
+    }
+
+    /// total calibration result using add, multiply and concatenate
+    fn part2(eqns: &Self::Input) -> Result<u64> {
+        let mut part2_total_calibration_result = 0;
+        for eqn in eqns.0.iter() {
+            let solver = EquationSolver::new(eqn.clone());
+            // dbg!(&solver);
+
+            // optimization: only try concatenation when add and multiply are not enough
+            let solve = solver
+                .solve(vec![Operator::Add, Operator::Multiply])
+                .or_else(|_| {
+                    solver.solve(vec![
+                        Operator::Add,
+                        Operator::Multiply,
+                        Operator::Concatenate,
+                    ])
+                });
+            if solve.is_ok() {
+                part2_total_calibration_result += eqn.lhs;
+            } else {
+                // dbg!(solve, eqn);
+            }
+        }
+
+        Ok(part2_total_calibration_result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    aoc_common::readme_tests! {
+        Day7, include_str!("../README.md");
+        test_1: part1 "For example:" => "the sum of the test values for the three equations listed above is";
+        test_2: part2 "For example:" => "produces the new total calibration result of";
+    }
+}